@@ -0,0 +1,385 @@
+//! Counting allocations of puzzles with a global allocator
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering::Relaxed},
+    time::Duration,
+};
+
+use crate::{report::Table, Analyzer, Failure, Phase};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+/// Bytes currently allocated. Negative if memory allocated before counting was enabled is freed
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+/// A global allocator counting allocations for the [AllocAnalyzer]
+///
+/// Wraps another allocator, by default the [System] one, and counts nothing until an
+/// [AllocAnalyzer] is created. Install it in the binary running the puzzles:
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+/// ```
+pub struct CountingAllocator<A = System> {
+    inner: A,
+}
+
+impl CountingAllocator {
+    pub const fn new() -> Self {
+        Self { inner: System }
+    }
+}
+
+impl Default for CountingAllocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A> CountingAllocator<A> {
+    /// Counts the allocations of `inner`
+    pub const fn wrap(inner: A) -> Self {
+        Self { inner }
+    }
+}
+
+fn on_alloc(size: usize) {
+    if ENABLED.load(Relaxed) {
+        ALLOCATIONS.fetch_add(1, Relaxed);
+        ALLOCATED.fetch_add(size, Relaxed);
+        let live = LIVE.fetch_add(size as isize, Relaxed) + size as isize;
+        PEAK.fetch_max(live, Relaxed);
+    }
+}
+
+fn on_dealloc(size: usize) {
+    if ENABLED.load(Relaxed) {
+        LIVE.fetch_sub(size as isize, Relaxed);
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAllocator<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc(layout) };
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { self.inner.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { self.inner.dealloc(ptr, layout) };
+        on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { self.inner.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            on_alloc(new_size);
+            on_dealloc(layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Allocations during a phase of a puzzle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations
+    pub allocations: usize,
+    /// Bytes allocated in total
+    pub bytes: usize,
+    /// Maximal number of bytes allocated at the same time, on top of the ones allocated before the phase
+    pub peak: usize,
+}
+
+impl AllocStats {
+    /// Combines the stats of two phases running one after the other
+    fn then(self, other: Self) -> Self {
+        Self {
+            allocations: self.allocations + other.allocations,
+            bytes: self.bytes + other.bytes,
+            peak: self.peak.max(other.peak),
+        }
+    }
+}
+
+/// Counters at the start of a phase
+#[derive(Debug, Clone, Copy)]
+struct Start {
+    allocations: usize,
+    bytes: usize,
+    live: isize,
+}
+
+impl Start {
+    fn now() -> Self {
+        let live = LIVE.load(Relaxed);
+        PEAK.store(live, Relaxed);
+        Self {
+            allocations: ALLOCATIONS.load(Relaxed),
+            bytes: ALLOCATED.load(Relaxed),
+            live,
+        }
+    }
+
+    fn stats(&self) -> AllocStats {
+        AllocStats {
+            allocations: ALLOCATIONS.load(Relaxed) - self.allocations,
+            bytes: ALLOCATED.load(Relaxed) - self.bytes,
+            peak: (PEAK.load(Relaxed) - self.live).max(0) as usize,
+        }
+    }
+}
+
+/// An analyzer that measures and prints the allocations of each phase
+///
+/// Requires the [CountingAllocator] to be installed as global allocator, otherwise no allocations
+/// are seen. As the allocator counts the allocations of all threads, days must not run in
+/// parallel while it measures them, so it is no [ParallelAnalyzer][crate::ParallelAnalyzer].
+pub struct AllocAnalyzer {
+    running_all: bool,
+    start: Option<Start>,
+    days: Vec<usize>,
+    alloc_parse: BTreeMap<usize, AllocStats>,
+    alloc_prepare: BTreeMap<usize, AllocStats>,
+    alloc_part: BTreeMap<(usize, usize), AllocStats>,
+    variant: Option<String>,
+    alloc_variants: BTreeMap<(usize, String, usize), AllocStats>,
+    failures: BTreeMap<(usize, Phase), String>,
+    timeouts: BTreeMap<(usize, usize), Duration>,
+}
+
+impl AllocAnalyzer {
+    /// Creates the analyzer and starts counting allocations
+    pub fn new() -> Self {
+        ENABLED.store(true, Relaxed);
+        Self {
+            running_all: false,
+            start: None,
+            days: vec![],
+            alloc_parse: Default::default(),
+            alloc_prepare: Default::default(),
+            alloc_part: Default::default(),
+            variant: None,
+            alloc_variants: Default::default(),
+            failures: Default::default(),
+            timeouts: Default::default(),
+        }
+    }
+
+    /// The measured allocations of a phase, or a label if the phase did not run or did not finish
+    fn cell(&self, day: usize, phase: Phase) -> Result<AllocStats, &'static str> {
+        if self.failures.contains_key(&(day, phase)) {
+            return Err("FAILED");
+        }
+        if let Phase::Part(part) = phase {
+            if self.timeouts.contains_key(&(day, part)) {
+                return Err("TIMEOUT");
+            }
+        }
+
+        let stats = match phase {
+            Phase::Parse => self.alloc_parse.get(&day),
+            Phase::Prepare => self.alloc_prepare.get(&day),
+            Phase::Part(part) => self.alloc_part.get(&(day, part)),
+        };
+        stats.cloned().ok_or("-")
+    }
+
+    /// The phases to show, which only include preparing if any day prepares
+    fn phases(&self) -> Vec<Phase> {
+        let preparing = !self.alloc_prepare.is_empty() || self.failures.keys().any(|(_, phase)| *phase == Phase::Prepare);
+        let mut phases = vec![Phase::Parse, Phase::Prepare, Phase::Part(1), Phase::Part(2)];
+        if !preparing {
+            phases.remove(1);
+        }
+        phases
+    }
+
+    /// Allocations of all finished phases of `day`
+    fn day_total(&self, day: usize) -> AllocStats {
+        [Phase::Parse, Phase::Prepare, Phase::Part(1), Phase::Part(2)]
+            .into_iter()
+            .filter_map(|phase| self.cell(day, phase).ok())
+            .fold(AllocStats::default(), AllocStats::then)
+    }
+
+    /// Allocations of `phase` of all days
+    fn phase_total(&self, phase: Phase) -> AllocStats {
+        self.days
+            .iter()
+            .filter_map(|day| self.cell(*day, phase).ok())
+            .fold(AllocStats::default(), AllocStats::then)
+    }
+
+    /// Names of the further variants run for `day`
+    fn variants(&self, day: usize) -> Vec<&str> {
+        let mut variants = self.alloc_variants.keys()
+            .filter(|(variant_day, _, _)| *variant_day == day)
+            .map(|(_, variant, _)| variant.as_str())
+            .collect::<Vec<_>>();
+        variants.dedup();
+        variants
+    }
+
+    fn report(&self) {
+        self.report_table("Allocations", |stats| format_count(stats.allocations));
+        self.report_table("Allocated", |stats| format_bytes(stats.bytes));
+        self.report_table("Peak", |stats| format_bytes(stats.peak));
+
+        for ((day, phase), message) in self.failures.iter() {
+            println!("Day {}, {} failed: {}", day, phase, message);
+        }
+        for ((day, part), timeout) in self.timeouts.iter() {
+            println!("Day {}, part {} timed out after {:?}", day, part, timeout);
+        }
+    }
+
+    /// Prints the table of one of the measured values, which `format` renders from the stats
+    fn report_table(&self, title: &str, format: impl Fn(AllocStats) -> String) {
+        let col = |cell: Result<AllocStats, &str>| match cell {
+            Ok(stats) => format(stats),
+            Err(label) => label.to_string(),
+        };
+        let phases = self.phases();
+        let mut columns = phases.iter().map(|phase| match phase {
+            Phase::Parse => "Parse".to_string(),
+            Phase::Prepare => "Prepare".to_string(),
+            Phase::Part(part) => format!("Part {}", part),
+        }).collect::<Vec<_>>();
+        columns.push("Total".to_string());
+        let columns = columns.iter().map(|column| (column.as_str(), 8)).collect::<Vec<_>>();
+
+        let table = Table::start(title, &columns);
+        let totals = phases.iter().map(|phase| self.phase_total(*phase)).collect::<Vec<_>>();
+        let total = totals.iter().copied().fold(AllocStats::default(), AllocStats::then);
+        table.row("Total", totals.into_iter().chain([total]).map(&format));
+        table.separator();
+        for &day in self.days.iter() {
+            let cells = phases.iter().map(|phase| col(self.cell(day, *phase)));
+            table.row(&day.to_string(), cells.chain([format(self.day_total(day))]));
+            for variant in self.variants(day) {
+                let cells = phases.iter().map(|phase| match phase {
+                    Phase::Part(part) => col(self.alloc_variants.get(&(day, variant.to_string(), *part)).cloned().ok_or("")),
+                    _ => String::new(),
+                });
+                table.row(&format!("{} {}", day, variant), cells.chain([String::new()]));
+            }
+        }
+        table.rule();
+    }
+}
+
+impl Default for AllocAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn format_count(count: usize) -> String {
+    if count >= 10_000_000 {
+        format!("{}M", count / 1_000_000)
+    } else if count >= 10_000 {
+        format!("{}k", count / 1_000)
+    } else {
+        count.to_string()
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1 << 30 {
+        format!("{}GB", bytes >> 30)
+    } else if bytes >= 1 << 20 {
+        format!("{}MB", bytes >> 20)
+    } else if bytes >= 1 << 10 {
+        format!("{}KB", bytes >> 10)
+    } else {
+        format!("{}B", bytes)
+    }
+}
+
+impl Analyzer for AllocAnalyzer {
+    fn before_all(&mut self) {
+        self.running_all = true;
+    }
+
+    fn after_all(&mut self) {
+        self.report();
+    }
+
+    fn before_day(&mut self, day: usize) {
+        self.days.push(day);
+    }
+
+    fn after_day(&mut self, _day: usize) {
+        if !self.running_all {
+            self.report();
+        }
+    }
+
+    fn before_parse(&mut self, _day: usize) {
+        self.start = Some(Start::now());
+    }
+
+    fn after_parse(&mut self, day: usize) {
+        self.alloc_parse.insert(day, self.start.take().unwrap().stats());
+    }
+
+    fn before_prepare(&mut self, _day: usize) {
+        self.start = Some(Start::now());
+    }
+
+    fn after_prepare(&mut self, day: usize) {
+        self.alloc_prepare.insert(day, self.start.take().unwrap().stats());
+    }
+
+    fn before_part(&mut self, _day: usize, _part: usize) {
+        self.start = Some(Start::now());
+    }
+
+    fn after_part(&mut self, day: usize, part: usize) {
+        let stats = self.start.take().unwrap().stats();
+        match self.variant.take() {
+            Some(variant) => self.alloc_variants.insert((day, variant, part), stats),
+            None => self.alloc_part.insert((day, part), stats),
+        };
+    }
+
+    fn on_variant(&mut self, _day: usize, _part: usize, variant: &str) {
+        self.variant = Some(variant.to_string());
+    }
+
+    fn on_failure(&mut self, day: usize, phase: Phase, failure: &Failure) {
+        self.failures.insert((day, phase), failure.to_string());
+    }
+
+    fn on_timeout(&mut self, day: usize, part: usize, timeout: Duration) {
+        self.timeouts.insert((day, part), timeout);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn format_cells() {
+        assert_eq!(format_count(1234), "1234");
+        assert_eq!(format_count(123_456), "123k");
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(3 << 20), "3MB");
+    }
+}
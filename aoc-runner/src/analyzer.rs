@@ -1,4 +1,12 @@
-use std::{time::{Instant, Duration}, collections::BTreeMap, fmt::Display};
+use std::{time::{Instant, Duration}, collections::BTreeMap, fmt::Display, fs::File};
+
+use crate::{
+    day::DEFAULT_VARIANT,
+    history::{Baseline, History},
+    report::{write_report, Record, Report, ReportFormat, Status, Table},
+    stats::Stats,
+    Answer, InputDigest, ParseError, Trace,
+};
 
 /// Scaffold metr an AoC runner
 pub trait Analyzer {
@@ -20,11 +28,124 @@ pub trait Analyzer {
     /// Called after parsing a puzzle's input
     fn after_parse(&mut self, _day: usize) {}
 
+    /// Called before preparing the state shared by both parts, for days setting
+    /// [PREPARE][crate::Day::PREPARE]
+    fn before_prepare(&mut self, _day: usize) {}
+
+    /// Called after preparing the state shared by both parts, for days setting
+    /// [PREPARE][crate::Day::PREPARE]
+    fn after_prepare(&mut self, _day: usize) {}
+
+    /// Called after [after_parse][Analyzer::after_parse] with the size and hash of the whole input,
+    /// whether parsing succeeded or not
+    ///
+    /// Streamed inputs are hashed while they are read, all other inputs once they were parsed. The
+    /// rest of a stream that was not parsed to its end is read afterwards, and if that fails, this
+    /// is not called.
+    fn on_input(&mut self, _day: usize, _input: &InputDigest) {}
+
     /// Called before running a puzle's part
     fn before_part(&mut self, _day: usize, _part: usize) {}
 
     /// Called after running a puzle's part
     fn after_part(&mut self, _day: usize, _part: usize) {}
+
+    /// Called before [before_part][Analyzer::before_part] if the part runs a further
+    /// [variant][crate::Variant] to cross-check the answer
+    fn on_variant(&mut self, _day: usize, _part: usize, _variant: &str) {}
+
+    /// Called with each [trace][crate::Trace] a puzzle's part sends while it runs, if the runner
+    /// [traces][crate::RunOptions::trace]
+    fn on_trace(&mut self, _day: usize, _part: usize, _trace: &Trace) {}
+
+    /// Called with the rendered answer of a puzzle's part
+    fn on_answer(&mut self, _day: usize, _part: usize, _answer: &Answer) {}
+
+    /// Called if a phase of a day's puzzle failed, e.g. panicked or could not parse its input
+    fn on_failure(&mut self, _day: usize, _phase: Phase, _failure: &Failure) {}
+
+    /// Called if a puzzle's part exceeded its timeout and was cancelled
+    fn on_timeout(&mut self, _day: usize, _part: usize, _timeout: Duration) {}
+
+    /// The [TimeAnalyzer] among this analyzer and the analyzers it is made up of, if there is one
+    ///
+    /// Used to configure the time analyzer of a set of analyzers, e.g. of one derived with
+    /// `#[analyzer(AllocAnalyzer, TimeAnalyzer)]`.
+    fn time_analyzer(&mut self) -> Option<&mut TimeAnalyzer> {
+        None
+    }
+}
+
+/// An analyzer ignoring all hooks, e.g. for unmeasured warm-up runs
+impl Analyzer for () {}
+
+/// An analyzer that can follow several days running at the same time
+///
+/// Each day gets its own fork of the analyzer, which is merged back once the day finished. Hooks
+/// are thus never called concurrently on the same instance.
+pub trait ParallelAnalyzer: Analyzer + Send {
+    /// Creates an analyzer for a single day of the current run
+    fn fork(&self) -> Self;
+
+    /// Merges the analyzer of a finished day back into this one
+    fn merge(&mut self, other: Self);
+}
+
+impl ParallelAnalyzer for () {
+    fn fork(&self) -> Self {}
+
+    fn merge(&mut self, _other: Self) {}
+}
+
+/// A phase of running a day's puzzle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Phase {
+    Parse,
+    /// Preparing the state shared by both parts, see [Day::prepare][crate::Day::prepare]
+    Prepare,
+    Part(usize),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Prepare => write!(f, "prepare"),
+            Phase::Part(part) => write!(f, "part {}", part),
+        }
+    }
+}
+
+/// Why a phase of a day's puzzle failed
+///
+/// Displays as a single line describing the failure.
+#[derive(Debug, Clone, Copy)]
+pub enum Failure<'a> {
+    /// The input could not be read
+    Read(&'a std::io::Error),
+    /// The input could not be parsed
+    Parse(&'a ParseError),
+    /// The phase panicked with the given message
+    Panic(&'a str),
+    /// The [variants][crate::Variant] of a part gave different answers, listed by name
+    Disagreement(&'a [(&'a str, Answer)]),
+}
+
+impl Display for Failure<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Read(err) => write!(f, "could not read input, {}", err),
+            Failure::Parse(err) => write!(f, "{}", err),
+            Failure::Panic(message) => write!(f, "{}", message),
+            Failure::Disagreement(answers) => {
+                write!(f, "variants disagree")?;
+                for (name, answer) in answers.iter() {
+                    write!(f, ", {} = {}", name, answer)?;
+                }
+                Ok(())
+            }
+        }
+    }
 }
 
 #[derive(Default)]
@@ -34,9 +155,24 @@ pub struct TimeAnalyzer {
     start_day: Option<Instant>,
     time_days: BTreeMap<usize, Duration>,
     start_parse: Option<Instant>,
-    time_parse: BTreeMap<usize, Duration>,
+    time_parse: BTreeMap<usize, Vec<Duration>>,
+    start_prepare: Option<Instant>,
+    time_prepare: BTreeMap<usize, Vec<Duration>>,
     start_part: Option<Instant>,
-    time_part: BTreeMap<(usize,usize), Duration>,
+    time_part: BTreeMap<(usize,usize), Vec<Duration>>,
+    variant: Option<String>,
+    time_variants: BTreeMap<(usize, String, usize), Duration>,
+    /// Whether the last part that ran added a sample to `time_part`, rather than a variant's time
+    sampled_part: bool,
+    answers: BTreeMap<(usize, usize), Answer>,
+    inputs: BTreeMap<usize, InputDigest>,
+    failures: BTreeMap<(usize, Phase), String>,
+    timeouts: BTreeMap<(usize, usize), Duration>,
+    report: Report,
+    history: Option<History>,
+    baseline: Option<Baseline>,
+    /// Slowdown in percent beyond which a phase is flagged when comparing to the baseline
+    threshold: f64,
 }
 
 /// A simple analyzer that measures and prints run times
@@ -45,67 +181,369 @@ impl TimeAnalyzer {
         TimeAnalyzer::default()
     }
 
+    /// Writes the report in another format or to a file, see [Report]
+    pub fn with_report(self, report: Report) -> Self {
+        Self { report, ..self }
+    }
+
+    /// Saves the run times and answers of each run to `history`
+    pub fn with_history(self, history: History) -> Self {
+        Self { history: Some(history), ..self }
+    }
+
+    /// Shows the change of each phase's run time compared to `baseline` and flags phases that slowed
+    /// down by more than `threshold` percent
+    pub fn compare_to(self, baseline: Baseline, threshold: f64) -> Self {
+        Self { baseline: Some(baseline), threshold, ..self }
+    }
+
     fn days(&self) -> Vec<usize> {
         self.time_days.keys().cloned().collect()
     }
 
     fn total_parse(&self) -> Duration {
-        self.days().iter().map(|day| self.time_parse.get(day).unwrap()).cloned().reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+        self.days().iter().map(|day| self.parse_time(*day).unwrap_or_default()).reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+    }
+
+    fn total_prepare(&self) -> Duration {
+        self.days().iter().filter_map(|day| self.prepare_time(*day))
+            .reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
     }
 
     fn total_part1(&self) -> Duration {
-        self.days().iter().filter_map(|day| self.time_part.get(&(*day, 1)))
-            .cloned().reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+        self.days().iter().filter_map(|day| self.part_time(*day, 1))
+            .reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
     }
 
     fn total_part2(&self) -> Duration {
-        self.days().iter().filter_map(|day| self.time_part.get(&(*day, 2)))
-            .cloned().reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+        self.days().iter().filter_map(|day| self.part_time(*day, 2))
+            .reduce(|a,b| a.saturating_add(b)).unwrap_or_default()
+    }
+
+    /// Names of the further variants run for `day`
+    fn variants(&self, day: usize) -> Vec<&str> {
+        let mut variants = self.time_variants.keys()
+            .filter(|(variant_day, _, _)| *variant_day == day)
+            .map(|(_, variant, _)| variant.as_str())
+            .collect::<Vec<_>>();
+        variants.dedup();
+        variants
     }
 
+    /// Summed time of all days
     fn total(&self) -> Duration {
-        self.time_all.unwrap_or(self.time_days.values().sum())
+        self.days().iter().filter_map(|day| self.day_time(*day)).sum()
+    }
+
+    /// Median time of parsing the input of `day`
+    fn parse_time(&self, day: usize) -> Option<Duration> {
+        Stats::of(self.time_parse.get(&day)?).map(|stats| stats.median)
+    }
+
+    /// Median time of preparing the state shared by both parts of `day`
+    fn prepare_time(&self, day: usize) -> Option<Duration> {
+        Stats::of(self.time_prepare.get(&day)?).map(|stats| stats.median)
+    }
+
+    /// Median time of running `part` of `day`
+    fn part_time(&self, day: usize, part: usize) -> Option<Duration> {
+        Stats::of(self.time_part.get(&(day, part))?).map(|stats| stats.median)
+    }
+
+    /// Time of running `day`, or the summed median time of its phases if it ran repeatedly
+    fn day_time(&self, day: usize) -> Option<Duration> {
+        if self.repeated(day) {
+            let parts = [1, 2].into_iter().filter_map(|part| self.part_time(day, part));
+            Some(self.parse_time(day).into_iter().chain(self.prepare_time(day)).chain(parts).sum())
+        } else {
+            self.time_days.get(&day).cloned()
+        }
+    }
+
+    /// Returns true if the phases of `day` were measured more than once
+    fn repeated(&self, day: usize) -> bool {
+        self.time_parse.get(&day).is_some_and(|samples| samples.len() > 1)
+    }
+
+    /// Statistics of a phase, if it was measured more than once
+    fn stats(&self, day: usize, phase: Phase) -> Option<Stats> {
+        let samples = match phase {
+            Phase::Parse => self.time_parse.get(&day),
+            Phase::Prepare => self.time_prepare.get(&day),
+            Phase::Part(part) => self.time_part.get(&(day, part)),
+        };
+        samples.filter(|samples| samples.len() > 1).and_then(|samples| Stats::of(samples))
+    }
+
+    /// The measured time of a phase, or a label if the phase did not run or did not finish
+    fn cell(&self, day: usize, phase: Phase) -> Result<Duration, &'static str> {
+        if self.failures.contains_key(&(day, phase)) {
+            return Err("FAILED");
+        }
+        if let Phase::Part(part) = phase {
+            if self.timeouts.contains_key(&(day, part)) {
+                return Err("TIMEOUT");
+            }
+        }
+
+        let time = match phase {
+            Phase::Parse => self.parse_time(day),
+            Phase::Prepare => self.prepare_time(day),
+            Phase::Part(part) => self.part_time(day, part),
+        };
+        time.ok_or("-")
     }
 
     fn report(&mut self) {
-        fn print_line(day: impl Display, parse: Duration, part1: Duration, part2: Duration, total: Duration) {
-            print!("| {:>6} |", day);
-            print_col(parse);
-            print_col(part1);
-            print_col(part2);
-            print_col(total);
-            print!("\n");
-        }
-
-        fn print_col(duration: Duration) {
-            if duration.as_secs() > 0 {
-                print!(" {:>7}s |", duration.as_secs());
-            } else if duration.as_millis() > 0 {
-                print!(" {:>6}ms |", duration.as_millis());
-            } else if duration.as_micros() > 0 {
-                print!(" {:>6}μs |", duration.as_micros());
+        let records = self.records();
+        match self.report.format {
+            ReportFormat::Table => self.print_table(&records),
+            format => {
+                let result = match &self.report.path {
+                    Some(path) => File::create(path).and_then(|mut file| write_report(&mut file, format, &records, self.time_all)),
+                    None => write_report(&mut std::io::stdout(), format, &records, self.time_all),
+                };
+                if let Err(err) = result {
+                    eprintln!("Could not write report: {}", err);
+                }
+            }
+        }
+
+        if let Some(history) = &self.history {
+            if let Err(err) = history.save(&records) {
+                eprintln!("Could not save run to history: {}", err);
+            }
+        }
+    }
+
+    /// The measurements of all phases and days, see [Record]
+    fn records(&self) -> Vec<Record> {
+        let status = |day: usize, phase: Phase| {
+            if self.failures.contains_key(&(day, phase)) {
+                Status::Failed
+            } else if matches!(phase, Phase::Part(part) if self.timeouts.contains_key(&(day, part))) {
+                Status::Timeout
             } else {
-                print!(" {:>6}ns |", duration.as_nanos());
+                Status::Ok
+            }
+        };
+
+        let mut records = vec![];
+        for day in self.days() {
+            let mut record = |phase: &'static str, variant: &str, status: Status, time: Option<Duration>, answer: Option<&Answer>, stats: Option<Stats>| {
+                records.push(Record {
+                    day,
+                    phase,
+                    variant: variant.to_string(),
+                    status,
+                    time,
+                    answer: answer.map(|answer| answer.to_string()),
+                    stats,
+                    input: self.inputs.get(&day).copied(),
+                });
+            };
+
+            let parse_status = status(day, Phase::Parse);
+            if parse_status != Status::Ok || self.time_parse.contains_key(&day) {
+                record("parse", DEFAULT_VARIANT, parse_status, self.parse_time(day), None, self.stats(day, Phase::Parse));
             }
+            let prepare_status = status(day, Phase::Prepare);
+            if prepare_status != Status::Ok || self.time_prepare.contains_key(&day) {
+                let stats = self.stats(day, Phase::Prepare);
+                record("prepare", DEFAULT_VARIANT, prepare_status, self.prepare_time(day), None, stats);
+            }
+            for (part, phase) in [(1, "part1"), (2, "part2")] {
+                let part_status = status(day, Phase::Part(part));
+                if part_status != Status::Ok || self.time_part.contains_key(&(day, part)) {
+                    let answer = self.answers.get(&(day, part));
+                    let stats = self.stats(day, Phase::Part(part));
+                    record(phase, DEFAULT_VARIANT, part_status, self.part_time(day, part), answer, stats);
+                }
+                for variant in self.variants(day) {
+                    if let Some(time) = self.time_variants.get(&(day, variant.to_string(), part)) {
+                        record(phase, variant, Status::Ok, Some(*time), None, None);
+                    }
+                }
+            }
+
+            let day_status = [Phase::Parse, Phase::Prepare, Phase::Part(1), Phase::Part(2)]
+                .into_iter()
+                .map(|phase| status(day, phase))
+                .find(|status| *status != Status::Ok)
+                .unwrap_or(Status::Ok);
+            record("day", DEFAULT_VARIANT, day_status, self.day_time(day), None, None);
         }
 
-        println!();
-        println!("+--------|----------|----------|----------|----------+");
-        println!("| Day    | Parse    | Part 1   | Part 2   | Total    |");
-        println!("+--------|----------|----------|----------|----------+");
-        print_line("Total", self.total_parse(), self.total_part1(), self.total_part2(), self.total());
-        println!("+----------------------------------------------------+");
-        for ref day in self.days() {
+        records
+    }
+
+    /// The change of `time` compared to the baseline's time of the same phase, e.g. `+12%`
+    ///
+    /// Ends with a `!` if the phase slowed down beyond the threshold.
+    fn change(&self, day: usize, phase: &str, variant: &str, time: Result<Duration, &str>) -> String {
+        let (Some(baseline), Ok(time)) = (&self.baseline, time) else {
+            return String::new();
+        };
+        let Some(base) = baseline.get(day, phase, variant, self.inputs.get(&day)) else {
+            return String::new();
+        };
+
+        let percent = slowdown(base, time);
+        format!("{:+.0}%{}", percent, if percent > self.threshold { "!" } else { "" })
+    }
+
+    /// Prints the report as a table with human-readable times
+    fn print_table(&self, records: &[Record]) {
+        let comparing = self.baseline.is_some();
+        // the prepare column is only shown if any day prepares
+        let preparing = !self.time_prepare.is_empty() || self.failures.keys().any(|(_, phase)| *phase == Phase::Prepare);
+        let mut columns = vec![];
+        for column in ["Parse", "Prepare", "Part 1", "Part 2", "Total"] {
+            if column == "Prepare" && !preparing {
+                continue;
+            }
+            columns.push((column, 8));
+            if comparing {
+                columns.push(("Δ", 6));
+            }
+        }
+        let table = Table::start("Day", &columns);
+        let print_line = |day: &str, mut cells: Vec<(Result<Duration, &str>, String)>| {
+            if !preparing {
+                drop(cells.remove(1));
+            }
+            let cells = cells.into_iter().flat_map(|(cell, change)| {
+                let cell = match cell {
+                    Ok(duration) => format_duration(duration),
+                    Err(label) => label.to_string(),
+                };
+                [Some(cell), comparing.then_some(change)]
+            });
+            table.row(day, cells.flatten());
+        };
+        let no_change = |cell| (cell, String::new());
+
+        print_line("Total", vec![
+            no_change(Ok(self.total_parse())),
+            no_change(Ok(self.total_prepare())),
+            no_change(Ok(self.total_part1())),
+            no_change(Ok(self.total_part2())),
+            no_change(Ok(self.total())),
+        ]);
+        if let Some(time_all) = self.time_all {
+            let mut cells = vec![no_change(Err("")); 4];
+            cells.push(no_change(Ok(time_all)));
+            print_line("Wall", cells);
+        }
+        table.separator();
+        for day in self.days() {
+            let cell = |phase: Phase, name: &str| {
+                let cell = self.cell(day, phase);
+                (cell, self.change(day, name, DEFAULT_VARIANT, cell))
+            };
+            let total = Ok(self.day_time(day).unwrap_or_default());
             print_line(
-                day,
-                self.time_parse.get(day).cloned().unwrap_or_default(),
-                self.time_part.get(&(*day, 1)).cloned().unwrap_or_default(),
-                self.time_part.get(&(*day, 2)).cloned().unwrap_or_default(),
-                self.time_days.get(day).cloned().unwrap_or_default(),
+                &day.to_string(),
+                vec![
+                    cell(Phase::Parse, "parse"),
+                    cell(Phase::Prepare, "prepare"),
+                    cell(Phase::Part(1), "part1"),
+                    cell(Phase::Part(2), "part2"),
+                    (total, self.change(day, "day", DEFAULT_VARIANT, total)),
+                ],
             );
+            for variant in self.variants(day) {
+                let cell = |part: usize, name: &str| {
+                    let time = self.time_variants.get(&(day, variant.to_string(), part)).cloned().ok_or("");
+                    (time, self.change(day, name, variant, time))
+                };
+                let cells = vec![no_change(Err("")), no_change(Err("")), cell(1, "part1"), cell(2, "part2"), no_change(Err(""))];
+                print_line(&format!("{} {}", day, variant), cells);
+            }
+        }
+        table.rule();
+        self.print_stats();
+
+        for ((day, phase), message) in self.failures.iter() {
+            println!("Day {}, {} failed: {}", day, phase, message);
+        }
+        for ((day, part), timeout) in self.timeouts.iter() {
+            println!("Day {}, part {} timed out after {:?}", day, part, timeout);
+        }
+        if let Some(baseline) = &self.baseline {
+            self.print_slowdowns(baseline, records);
+        }
+    }
+
+    /// Prints the statistics of all phases measured more than once
+    fn print_stats(&self) {
+        let days = self.days().into_iter().filter(|day| self.repeated(*day)).collect::<Vec<_>>();
+        if days.is_empty() {
+            return;
+        }
+
+        let columns = ["Samples", "Min", "Median", "Mean", "p95", "Stddev"].map(|column| (column, 8));
+        let table = Table::start("Day", &columns);
+        for day in days {
+            for phase in [Phase::Parse, Phase::Prepare, Phase::Part(1), Phase::Part(2)] {
+                let Some(stats) = self.stats(day, phase) else {
+                    continue;
+                };
+                let times = [stats.min, stats.median, stats.mean, stats.p95, stats.stddev].map(format_duration);
+                table.row(&format!("{} {}", day, phase), [stats.samples.to_string()].into_iter().chain(times));
+            }
         }
-        println!("+--------|----------|----------|----------|----------+");
+        table.rule();
     }
+
+    /// Prints the phases that slowed down beyond the threshold compared to `baseline`
+    fn print_slowdowns(&self, baseline: &Baseline, records: &[Record]) {
+        for record in records {
+            let (Status::Ok, Some(time)) = (record.status, record.time) else {
+                continue;
+            };
+            let Some(base) = baseline.get(record.day, record.phase, &record.variant, record.input.as_ref()) else {
+                continue;
+            };
+
+            let percent = slowdown(base, time);
+            if percent > self.threshold {
+                let variant = match record.variant.as_str() {
+                    DEFAULT_VARIANT => String::new(),
+                    variant => format!(" ({})", variant),
+                };
+                println!(
+                    "Day {}, {}{} slowed down by {:.0}% compared to {}: {} -> {} (+{})",
+                    record.day,
+                    record.phase,
+                    variant,
+                    percent,
+                    baseline.description,
+                    format_duration(base),
+                    format_duration(time),
+                    format_duration(time.saturating_sub(base)),
+                );
+            }
+        }
+    }
+}
+
+/// Renders `duration` in its largest unit, e.g. `12ms`
+fn format_duration(duration: Duration) -> String {
+    if duration.as_secs() > 0 {
+        format!("{}s", duration.as_secs())
+    } else if duration.as_millis() > 0 {
+        format!("{}ms", duration.as_millis())
+    } else if duration.as_micros() > 0 {
+        format!("{}μs", duration.as_micros())
+    } else {
+        format!("{}ns", duration.as_nanos())
+    }
+}
+
+/// Change from `base` to `time` in percent, positive if `time` is slower
+fn slowdown(base: Duration, time: Duration) -> f64 {
+    (time.as_secs_f64() - base.as_secs_f64()) / base.as_secs_f64().max(f64::MIN_POSITIVE) * 100.0
 }
 
 impl Analyzer for TimeAnalyzer {
@@ -135,7 +573,15 @@ impl Analyzer for TimeAnalyzer {
     }
 
     fn after_parse(&mut self, day: usize) {
-        self.time_parse.insert(day, self.start_parse.unwrap().elapsed());
+        self.time_parse.entry(day).or_default().push(self.start_parse.unwrap().elapsed());
+    }
+
+    fn before_prepare(&mut self, _day: usize) {
+        self.start_prepare = Some(Instant::now());
+    }
+
+    fn after_prepare(&mut self, day: usize) {
+        self.time_prepare.entry(day).or_default().push(self.start_prepare.unwrap().elapsed());
     }
 
     fn before_part(&mut self, _day: usize, _part: usize) {
@@ -143,6 +589,63 @@ impl Analyzer for TimeAnalyzer {
     }
 
     fn after_part(&mut self, day: usize, part: usize) {
-        self.time_part.insert((day, part), self.start_part.unwrap().elapsed());
+        let time = self.start_part.unwrap().elapsed();
+        self.sampled_part = self.variant.is_none();
+        match self.variant.take() {
+            Some(variant) => {
+                self.time_variants.insert((day, variant, part), time);
+            }
+            None => self.time_part.entry((day, part)).or_default().push(time),
+        }
+    }
+
+    fn on_variant(&mut self, _day: usize, _part: usize, variant: &str) {
+        self.variant = Some(variant.to_string());
+    }
+
+    fn on_answer(&mut self, day: usize, part: usize, answer: &Answer) {
+        self.answers.insert((day, part), answer.clone());
+    }
+
+    fn on_input(&mut self, day: usize, input: &InputDigest) {
+        self.inputs.insert(day, *input);
+    }
+
+    fn on_failure(&mut self, day: usize, phase: Phase, failure: &Failure) {
+        self.failures.insert((day, phase), failure.to_string());
+    }
+
+    fn time_analyzer(&mut self) -> Option<&mut TimeAnalyzer> {
+        Some(self)
+    }
+
+    fn on_timeout(&mut self, day: usize, part: usize, timeout: Duration) {
+        // a timed out part only ran as long as the timeout, which is no sample of its run time
+        if self.sampled_part {
+            self.time_part.get_mut(&(day, part)).and_then(|samples| samples.pop());
+        }
+        self.timeouts.insert((day, part), timeout);
+    }
+}
+
+impl ParallelAnalyzer for TimeAnalyzer {
+    fn fork(&self) -> Self {
+        Self {
+            // a fork never reports on its own
+            start_all: self.start_all,
+            ..Default::default()
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.time_days.extend(other.time_days);
+        self.time_parse.extend(other.time_parse);
+        self.time_prepare.extend(other.time_prepare);
+        self.time_part.extend(other.time_part);
+        self.time_variants.extend(other.time_variants);
+        self.answers.extend(other.answers);
+        self.inputs.extend(other.inputs);
+        self.failures.extend(other.failures);
+        self.timeouts.extend(other.timeouts);
     }
 }
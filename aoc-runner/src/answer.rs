@@ -0,0 +1,156 @@
+//! The answer of a puzzle's part
+
+use std::fmt::Display;
+
+/// The answer of a puzzle's part
+///
+/// Numbers are normalized, so equal numbers always use the same variant: [Int][Answer::Int] if the
+/// number fits into an `i64`, [BigInt][Answer::BigInt] otherwise.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    /// A number fitting into an `i64`
+    Int(i64),
+    /// A number too large for an `i64`, as decimal digits with an optional leading `-`
+    BigInt(String),
+    /// A textual answer
+    Text(String),
+    /// ASCII art, one string per row
+    Grid(Vec<String>),
+    /// The part has no answer (yet)
+    Unsolved,
+}
+
+impl Answer {
+    /// Creates a [Grid][Answer::Grid] answer from rows of pixels, drawing set pixels as `#`
+    pub fn grid<R: AsRef<[bool]>>(rows: impl IntoIterator<Item = R>) -> Self {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.as_ref().iter().map(|&set| if set { '#' } else { '.' }).collect())
+            .collect();
+        Answer::Grid(rows)
+    }
+
+    /// Returns true if this answer is a number
+    pub fn is_number(&self) -> bool {
+        matches!(self, Answer::Int(_) | Answer::BigInt(_))
+    }
+
+    /// Returns true if this answer matches the `expected` one, given in its textual form
+    ///
+    /// Numbers are compared numerically (so `"042"` matches `42`), text ignoring surrounding
+    /// whitespace and grids row by row ignoring trailing whitespace. An unsolved answer matches
+    /// nothing.
+    pub fn matches(&self, expected: &str) -> bool {
+        let expected = expected.trim();
+        match self {
+            Answer::Int(value) => expected.parse::<i128>().is_ok_and(|it| it == *value as i128),
+            Answer::BigInt(digits) => normalize_number(expected).is_some_and(|it| it == *digits),
+            Answer::Text(text) => text.trim() == expected,
+            Answer::Grid(rows) => rows
+                .iter()
+                .map(|row| row.trim_end())
+                .eq(expected.lines().map(|row| row.trim())),
+            Answer::Unsolved => false,
+        }
+    }
+}
+
+/// Normalizes a decimal number (sign, leading zeros), returning `None` if `s` is no number
+fn normalize_number(s: &str) -> Option<String> {
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits = digits.trim_start_matches('0');
+    Some(match (negative, digits) {
+        (_, "") => "0".to_string(),
+        (true, digits) => format!("-{}", digits),
+        (false, digits) => digits.to_string(),
+    })
+}
+
+/// Renders numbers and text as is, grids as multiple lines and unsolved answers as `—`
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(value) => write!(f, "{}", value),
+            Answer::BigInt(digits) => write!(f, "{}", digits),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+            Answer::Unsolved => write!(f, "—"),
+        }
+    }
+}
+
+macro_rules! from_small_int {
+    ($($t: ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::Int(value as i64)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! from_large_int {
+    ($($t: ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    match i64::try_from(value) {
+                        Ok(value) => Answer::Int(value),
+                        Err(_) => Answer::BigInt(value.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_small_int!(i8, i16, i32, i64, u8, u16, u32);
+from_large_int!(isize, usize, u64, i128, u128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Answer::Unsolved)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_numbers() {
+        assert_eq!(Answer::from(42u128), Answer::Int(42));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt("18446744073709551615".to_string()));
+    }
+
+    #[test]
+    fn match_expected() {
+        assert!(Answer::from(42u16).matches("042"));
+        assert!(Answer::from(u64::MAX).matches("18446744073709551615"));
+        assert!(!Answer::from(41u16).matches("42"));
+        assert!(Answer::from("abc").matches(" abc\n"));
+        assert!(Answer::grid([[true, false], [false, true]]).matches("#.\n.#\n"));
+        assert!(!Answer::Unsolved.matches("0"));
+    }
+}
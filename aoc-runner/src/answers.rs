@@ -0,0 +1,429 @@
+//! On-disk store of known puzzle answers
+//!
+//! Answers are kept as one small TOML file per day (e.g. `answers/07.toml`):
+//! ```toml
+//! part1 = "1234"
+//! part2 = "5678"
+//! ```
+//!
+//! Grid answers are recorded row by row, separated by `\n`. Answers the website rejected are
+//! recorded next to them, so they are never submitted again:
+//! ```toml
+//! part2_wrong = ["5000"]
+//! part2_too_low = "4000"
+//! part2_too_high = "6000"
+//! ```
+
+use std::{fmt::Display, path::PathBuf};
+
+use crate::Answer;
+
+/// The recorded answers of a single day
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    /// Answers of part 1 the website rejected
+    pub rejected1: Rejected,
+    /// Answers of part 2 the website rejected
+    pub rejected2: Rejected,
+}
+
+/// Why the website rejected an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rejection {
+    Wrong,
+    TooLow,
+    TooHigh,
+}
+
+/// The answers of a part the website rejected
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rejected {
+    /// All rejected answers, including the ones out of bounds
+    pub wrong: Vec<String>,
+    /// The greatest answer that was too low
+    pub too_low: Option<String>,
+    /// The smallest answer that was too high
+    pub too_high: Option<String>,
+}
+
+impl Rejected {
+    /// Tells why `answer` is known to be wrong, if it is
+    ///
+    /// Numbers are also known to be wrong if they are not above an answer that was too low, or not
+    /// below one that was too high.
+    pub fn reason(&self, answer: &Answer) -> Option<String> {
+        if let Some(wrong) = self.wrong.iter().find(|wrong| answer.matches(wrong)) {
+            return Some(format!("{} was rejected before", wrong));
+        }
+
+        let number = answer.to_string().parse::<i128>().ok().filter(|_| answer.is_number())?;
+        let bound = |bound: &Option<String>| bound.as_ref().and_then(|bound| bound.trim().parse::<i128>().ok());
+        match (bound(&self.too_low), bound(&self.too_high)) {
+            (Some(low), _) if number <= low => Some(format!("{} was too low", low)),
+            (_, Some(high)) if number >= high => Some(format!("{} was too high", high)),
+            _ => None,
+        }
+    }
+
+    /// Records that `answer` was rejected
+    pub fn add(&mut self, answer: &str, rejection: Rejection) {
+        if !self.wrong.iter().any(|wrong| wrong == answer) {
+            self.wrong.push(answer.to_string());
+        }
+
+        let number = answer.trim().parse::<i128>().ok();
+        let tighter = |bound: &Option<String>, closer: fn(i128, i128) -> bool| {
+            let bound = bound.as_ref().and_then(|bound| bound.trim().parse::<i128>().ok());
+            number.is_some_and(|number| bound.is_none_or(|bound| closer(number, bound)))
+        };
+        match rejection {
+            Rejection::TooLow if tighter(&self.too_low, |number, low| number > low) => {
+                self.too_low = Some(answer.to_string())
+            }
+            Rejection::TooHigh if tighter(&self.too_high, |number, high| number < high) => {
+                self.too_high = Some(answer.to_string())
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Answers {
+    /// Parses the contents of an answers file
+    ///
+    /// Only the subset of TOML needed for answers is understood: `key = "string"`,
+    /// `key = integer` and `key = [...]` pairs of such values, comments and blank lines. Strings may
+    /// contain the escapes `\"`, `\\` and `\n`. Unknown keys are ignored.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut answers = Self::default();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {}: expected 'key = value'", idx + 1))?;
+            let value = value.trim();
+            if let Some(items) = value.strip_prefix('[') {
+                let items = items.strip_suffix(']').ok_or_else(|| format!("line {}: unterminated array", idx + 1))?;
+                let items = parse_array(items).map_err(|err| format!("line {}: {}", idx + 1, err))?;
+                match key.trim() {
+                    "part1_wrong" => answers.rejected1.wrong = items,
+                    "part2_wrong" => answers.rejected2.wrong = items,
+                    _ => {}
+                }
+                continue;
+            }
+            let value = parse_value(value).map_err(|err| format!("line {}: {}", idx + 1, err))?;
+
+            match key.trim() {
+                "part1" => answers.part1 = Some(value),
+                "part2" => answers.part2 = Some(value),
+                "part1_too_low" => answers.rejected1.too_low = Some(value),
+                "part1_too_high" => answers.rejected1.too_high = Some(value),
+                "part2_too_low" => answers.rejected2.too_low = Some(value),
+                "part2_too_high" => answers.rejected2.too_high = Some(value),
+                _ => {}
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// The recorded answer of `part`, if any
+    pub fn get(&self, part: usize) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records the answer of `part`
+    pub fn set(&mut self, part: usize, answer: &str) {
+        match part {
+            1 => self.part1 = Some(answer.to_string()),
+            _ => self.part2 = Some(answer.to_string()),
+        }
+    }
+
+    /// The answers of `part` the website rejected
+    pub fn rejected(&self, part: usize) -> &Rejected {
+        match part {
+            1 => &self.rejected1,
+            _ => &self.rejected2,
+        }
+    }
+
+    /// The answers of `part` the website rejected, to record another one
+    pub fn rejected_mut(&mut self, part: usize) -> &mut Rejected {
+        match part {
+            1 => &mut self.rejected1,
+            _ => &mut self.rejected2,
+        }
+    }
+
+    /// Renders the answers in the format understood by [parse][Answers::parse]
+    pub fn to_toml(&self) -> String {
+        self.update_toml("")
+    }
+
+    /// Renders the answers into the existing contents of an answers file
+    ///
+    /// Only the lines of the keys holding answers are replaced, removed or appended. Comments,
+    /// blank lines and unknown keys are kept as they are.
+    pub fn update_toml(&self, content: &str) -> String {
+        let mut entries = self.entries();
+        let mut toml = String::new();
+        for line in content.lines() {
+            let key = match line.trim_start().starts_with('#') {
+                true => None,
+                false => line.split_once('=').map(|(key, _)| key.trim()),
+            };
+            match key.and_then(|key| entries.iter_mut().find(|(owned, _)| owned == key)) {
+                Some((key, value)) => {
+                    if let Some(value) = value.take() {
+                        toml.push_str(&format!("{} = {}\n", key, value));
+                    }
+                }
+                None => {
+                    toml.push_str(line);
+                    toml.push('\n');
+                }
+            }
+        }
+        for (key, value) in entries {
+            if let Some(value) = value {
+                toml.push_str(&format!("{} = {}\n", key, value));
+            }
+        }
+        toml
+    }
+
+    /// All keys holding answers, with their rendered values if recorded
+    fn entries(&self) -> Vec<(String, Option<String>)> {
+        let mut entries = vec![];
+        for (part, answer, rejected) in [(1, &self.part1, &self.rejected1), (2, &self.part2, &self.rejected2)] {
+            let wrong = rejected.wrong.iter().map(|wrong| quote(wrong)).collect::<Vec<_>>();
+            entries.push((format!("part{}", part), answer.as_deref().map(quote)));
+            entries.push((
+                format!("part{}_wrong", part),
+                Some(format!("[{}]", wrong.join(", "))).filter(|_| !wrong.is_empty()),
+            ));
+            entries.push((format!("part{}_too_low", part), rejected.too_low.as_deref().map(quote)));
+            entries.push((format!("part{}_too_high", part), rejected.too_high.as_deref().map(quote)));
+        }
+        entries
+    }
+}
+
+/// Parses a string or integer value
+fn parse_value(value: &str) -> Result<String, &'static str> {
+    match value.strip_prefix('"') {
+        Some(value) => {
+            let value = value.strip_suffix('"').ok_or("unterminated string")?;
+            unescape(value).ok_or("invalid escape")
+        }
+        None => Ok(value.to_string()),
+    }
+}
+
+/// Parses the comma-separated values of an array, given without its brackets
+fn parse_array(mut items: &str) -> Result<Vec<String>, &'static str> {
+    let mut values = vec![];
+    loop {
+        items = items.trim_start();
+        if items.is_empty() {
+            return Ok(values);
+        }
+        let len = match items.strip_prefix('"') {
+            // the closing quote is the first one not escaped
+            Some(string) => {
+                let mut escaped = false;
+                let end = string.find(|c| {
+                    let closing = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    closing
+                });
+                end.ok_or("unterminated string")? + 2
+            }
+            None => items.find(',').unwrap_or(items.len()),
+        };
+        values.push(parse_value(items[..len].trim())?);
+        items = items[len..].trim_start();
+        items = match items.strip_prefix(',') {
+            Some(items) => items,
+            None if items.is_empty() => items,
+            None => return Err("expected ','"),
+        };
+    }
+}
+
+/// Quotes a string value, escaping it as needed
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Resolves the escapes of a string value, returning `None` for unknown ones
+fn unescape(s: &str) -> Option<String> {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next()? {
+            '"' => result.push('"'),
+            '\\' => result.push('\\'),
+            'n' => result.push('\n'),
+            _ => return None,
+        }
+    }
+    Some(result)
+}
+
+/// A directory of answer files, one per day
+#[derive(Debug, Clone)]
+pub struct AnswerStore {
+    dir: PathBuf,
+}
+
+impl AnswerStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Path of the answers file of `day`
+    pub fn path(&self, day: usize) -> PathBuf {
+        self.dir.join(format!("{:0>2}.toml", day))
+    }
+
+    /// Loads the recorded answers of `day`
+    ///
+    /// A missing file yields empty [Answers].
+    pub fn load(&self, day: usize) -> Result<Answers, String> {
+        let path = self.path(day);
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Answers::parse(&content).map_err(|err| format!("{}: {}", path.display(), err)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(format!("{}: {}", path.display(), err)),
+        }
+    }
+
+    /// Records the answers of `day` in its answers file
+    ///
+    /// Comments and unknown keys of the file are kept, see [update_toml][Answers::update_toml].
+    pub fn save(&self, day: usize, answers: &Answers) -> Result<(), String> {
+        let path = self.path(day);
+        let content = match std::fs::read_to_string(&path) {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            content => content,
+        };
+        content
+            .and_then(|content| {
+                std::fs::create_dir_all(&self.dir)?;
+                std::fs::write(&path, answers.update_toml(&content))
+            })
+            .map_err(|err| format!("{}: {}", path.display(), err))
+    }
+}
+
+/// Outcome of comparing a computed answer to the recorded one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// The computed answer matches the recorded one
+    Pass,
+    /// The computed answer differs from the recorded one
+    Fail { expected: String, actual: String },
+    /// There is no recorded answer to compare to
+    Missing,
+    /// The part is not solved, so there is nothing to compare
+    Unsolved,
+}
+
+impl Verdict {
+    /// Compares `actual` to the `expected` answer, see [Answer::matches]
+    pub fn check(expected: Option<&str>, actual: &Answer) -> Self {
+        match expected {
+            _ if *actual == Answer::Unsolved => Verdict::Unsolved,
+            Some(expected) if actual.matches(expected) => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail { .. } => "FAIL",
+            Verdict::Missing => "MISSING",
+            Verdict::Unsolved => "—",
+        };
+        f.pad(s)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_answers() {
+        let answers = Answers::parse("# day 1\npart1 = \"1234\"\n\npart2 = 56\n").unwrap();
+        assert_eq!(answers.get(1), Some("1234"));
+        assert_eq!(answers.get(2), Some("56"));
+        assert!(Answers::parse("part1 = \"1234").is_err());
+        let answers = Answers::parse("part1 = \"#.\\n.#\"").unwrap();
+        assert_eq!(answers.get(1), Some("#.\n.#"));
+    }
+
+    #[test]
+    fn record_rejected_answers() {
+        let mut answers = Answers::parse("part1 = 7\npart2_wrong = [\"a, \\\"b\\\"\", 12]\npart2_too_low = 10\n").unwrap();
+        assert_eq!(answers.rejected2.wrong, vec!["a, \"b\"", "12"]);
+        answers.rejected_mut(2).add("30", Rejection::TooHigh);
+        answers.rejected_mut(2).add("40", Rejection::TooHigh);
+        answers.rejected_mut(2).add("8", Rejection::TooLow);
+        answers.rejected_mut(2).add("15", Rejection::Wrong);
+        assert_eq!(Answers::parse(&answers.to_toml()), Ok(answers.clone()));
+
+        let rejected = answers.rejected(2);
+        assert_eq!((rejected.too_low.as_deref(), rejected.too_high.as_deref()), (Some("10"), Some("30")));
+        assert_eq!(rejected.reason(&Answer::Int(12)), Some("12 was rejected before".to_string()));
+        assert_eq!(rejected.reason(&Answer::Int(9)), Some("10 was too low".to_string()));
+        assert_eq!(rejected.reason(&Answer::Int(35)), Some("30 was too high".to_string()));
+        assert!(rejected.reason(&Answer::Text("a, \"b\"".to_string())).is_some());
+        assert_eq!(rejected.reason(&Answer::Int(20)), None);
+        assert_eq!(answers.rejected(1).reason(&Answer::Int(20)), None);
+    }
+
+    #[test]
+    fn keep_comments_and_unknown_keys() {
+        let content = "# day 7\npart1 = 7\nnote = \"slow\"\npart1 = 8\n\npart2_too_low = 10\n";
+        let mut answers = Answers::parse(content).unwrap();
+        answers.rejected_mut(2).add("12", Rejection::Wrong);
+        answers.set(2, "20");
+        assert_eq!(
+            answers.update_toml(content),
+            "# day 7\npart1 = \"8\"\nnote = \"slow\"\n\npart2_too_low = \"10\"\npart2 = \"20\"\npart2_wrong = [\"12\"]\n"
+        );
+    }
+
+    #[test]
+    fn check_verdict() {
+        assert_eq!(Verdict::check(Some("42"), &Answer::Int(42)), Verdict::Pass);
+        assert_eq!(Verdict::check(None, &Answer::Int(42)), Verdict::Missing);
+        assert_eq!(Verdict::check(Some("42"), &Answer::Unsolved), Verdict::Unsolved);
+        assert!(matches!(Verdict::check(Some("41"), &Answer::Int(42)), Verdict::Fail { .. }));
+    }
+}
@@ -0,0 +1,182 @@
+//! Collects a crate's days at build time, to be used from its build script
+//!
+//! # Example
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     aoc_runner::build::collect_days("src/days", "days.rs");
+//! }
+//!
+//! // src/days/mod.rs
+//! pub mod day01;
+//! pub mod day02;
+//!
+//! include!(concat!(env!("OUT_DIR"), "/days.rs"));
+//! ```
+
+use std::{fs, path::Path};
+
+/// Scans the modules in `dir` for structs annotated with `#[aoc(day = N)]` and generates a function
+/// `registry()` returning a [Registry][crate::Registry] of all of them
+///
+/// The function is written to `file_name` in `OUT_DIR` and refers to the structs relative to the
+/// module including it, so each file in `dir` must be declared as a module of the same name.
+///
+/// # Panics
+///
+/// If an annotation is not followed by a struct, which then would be missing from the registry.
+pub fn collect_days(dir: impl AsRef<Path>, file_name: &str) {
+    let dir = dir.as_ref();
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut paths = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("Could not read {}: {}", dir.display(), err))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|it| it == "rs") && !path.ends_with("mod.rs"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    let mut registrations = String::new();
+    for path in paths {
+        let module = path.file_stem().unwrap().to_string_lossy();
+        let content = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("Could not read {}: {}", path.display(), err));
+        let structs = annotated_structs(&content).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+        for ident in structs {
+            registrations += &format!("    registry.register::<{}::{}>();\n", module, ident);
+        }
+    }
+
+    let code = format!(
+        "/// Creates a registry of all days annotated with `#[aoc(day = N)]`
+pub fn registry() -> ::aoc_runner::Registry {{
+    let mut registry = ::aoc_runner::Registry::new();
+{}    registry
+}}
+",
+        registrations
+    );
+
+    let out_dir = std::env::var("OUT_DIR").expect("collect_days must be called from a build script");
+    let out_path = Path::new(&out_dir).join(file_name);
+    fs::write(&out_path, code).unwrap_or_else(|err| panic!("Could not write {}: {}", out_path.display(), err));
+}
+
+/// Returns the names of all structs in `content` annotated with `#[aoc(...)]`
+///
+/// Other attributes, comments and blank lines may appear between the annotation and the struct,
+/// and attributes may span several lines. Fails if an annotation is not followed by a struct, as
+/// its day would be missing from the registry.
+fn annotated_structs(content: &str) -> Result<Vec<&str>, String> {
+    let not_followed = |line: usize| format!("line {}: #[aoc] is not followed by a struct", line + 1);
+
+    let mut structs = vec![];
+    // the line of the annotation whose struct is yet to come
+    let mut annotated = None;
+    // the number of brackets left open by an attribute continuing on the next line
+    let mut depth = 0;
+    for (idx, line) in content.lines().enumerate() {
+        let mut rest = line.trim();
+        while depth > 0 || rest.starts_with("#[") {
+            if depth == 0 && (rest.starts_with("#[aoc(") || rest.starts_with("#[derive_aoc_runner::aoc(")) {
+                if let Some(line) = annotated {
+                    return Err(not_followed(line));
+                }
+                annotated = Some(idx);
+            }
+            let end = rest.char_indices().find_map(|(idx, c)| {
+                match c {
+                    '[' => depth += 1,
+                    ']' => depth -= 1,
+                    _ => {}
+                }
+                (depth == 0 && c == ']').then_some(idx + 1)
+            });
+            rest = match end {
+                Some(end) => rest[end..].trim_start(),
+                None => break,
+            };
+        }
+        if depth > 0 || rest.is_empty() || rest.starts_with("//") {
+            continue;
+        }
+        if let Some(line) = annotated.take() {
+            structs.push(struct_name(rest).ok_or_else(|| not_followed(line))?);
+        }
+    }
+
+    match annotated {
+        Some(line) => Err(not_followed(line)),
+        None => Ok(structs),
+    }
+}
+
+/// The name of the struct declared by `line`, e.g. `Day08` for `pub(crate) struct Day08<'input> {`
+fn struct_name(line: &str) -> Option<&str> {
+    let mut rest = line;
+    if let Some(after_pub) = rest.strip_prefix("pub") {
+        rest = match after_pub.strip_prefix('(') {
+            Some(restriction) => &restriction[restriction.find(')')? + 1..],
+            None => after_pub,
+        };
+        if !rest.starts_with(char::is_whitespace) {
+            return None;
+        }
+    }
+    let name = rest.trim_start().strip_prefix("struct")?;
+    if !name.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let name = name.trim_start();
+    let end = name.find(|c: char| !c.is_alphanumeric() && c != '_').unwrap_or(name.len());
+    (end > 0).then(|| &name[..end])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn find_annotated_structs() {
+        let content = "
+#[aoc(day = 8)]
+#[derive(Default)]
+pub struct Day08<const N: usize = 1000> {
+    boxes: Vec<Point>,
+}
+
+struct Point(u64, u64, u64);
+";
+        assert_eq!(annotated_structs(content), Ok(vec!["Day08"]));
+
+        let content = "
+#[aoc(day = 11)]
+/// This struct holds the devices
+#[derive(Default)]
+pub(crate) struct Day11<'input> {
+    devices: Vec<&'input str>,
+}
+";
+        assert_eq!(annotated_structs(content), Ok(vec!["Day11"]));
+
+        let content = "
+#[derive(Default)] #[aoc(
+    day = 3,
+)]
+// the batteries
+#[derive(Clone)] pub struct Day03;
+";
+        assert_eq!(annotated_structs(content), Ok(vec!["Day03"]));
+        assert_eq!(annotated_structs("#[aoc(day = 1)] struct Day01;"), Ok(vec!["Day01"]));
+
+        let content = "
+#[aoc(day = 5)]
+fn solve() {}
+";
+        assert_eq!(annotated_structs(content), Err("line 2: #[aoc] is not followed by a struct".to_string()));
+        assert!(annotated_structs("#[aoc(day = 5)]\n#[derive(Default)]\n").is_err());
+        assert_eq!(struct_name("// a struct holding"), None);
+        assert_eq!(struct_name("structure: Vec<u8>,"), None);
+        assert_eq!(struct_name("struct Point(u64, u64);"), Some("Point"));
+    }
+}
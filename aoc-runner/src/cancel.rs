@@ -0,0 +1,58 @@
+//! Cooperative cancellation of long running parts
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+/// A flag the runner raises when a part exceeds its timeout
+///
+/// Long running solvers can poll [is_cancelled][CancellationToken::is_cancelled] and return early.
+/// The answer of a cancelled part is discarded, so any value may be returned.
+///
+/// # Example
+/// ```ignore
+/// fn part2(&mut self) -> Self::Result2 {
+///     let token = CancellationToken::current();
+///     self.items.par_iter().map(|item| solve(item, &token)).sum()
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The token of the part running on the current thread
+    ///
+    /// Returns a token that is never cancelled if the part runs without a timeout. Worker threads
+    /// spawned by the part (e.g. by rayon) do not inherit the token, so it must be obtained before
+    /// and passed to them.
+    pub fn current() -> Self {
+        CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+    }
+
+    /// Makes this token the [current][CancellationToken::current] one of this thread
+    pub(crate) fn set_current(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+    }
+
+    /// Requests cancellation
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true if cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
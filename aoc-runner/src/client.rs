@@ -0,0 +1,384 @@
+//! Client of the Advent of Code website, downloading inputs into an [InputDir] and submitting
+//! answers
+//!
+//! Requests are authenticated with the session cookie of a logged in user and rate limited, so
+//! the site is never hit more than once per [interval][Client::with_interval]. Downloaded inputs
+//! are cached and never downloaded again.
+
+use std::{
+    fmt::Display,
+    fs,
+    io::Read,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{InputDir, Rejection};
+
+/// Where the website is found unless another base URL is given
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// How a requested input was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was cached before, no request was sent
+    Cached,
+    /// The input was downloaded
+    Downloaded,
+}
+
+/// The website's response to a submitted answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submission {
+    Correct,
+    Rejected(Rejection),
+    /// Another answer was submitted too recently, so this one was not checked. Holds the time left
+    /// to wait, if the response tells it
+    Wait(Option<Duration>),
+    /// The part is solved already or not unlocked yet
+    WrongLevel,
+}
+
+impl Submission {
+    /// Parses the page the website responds with
+    pub fn parse(page: &str) -> Result<Self, String> {
+        let text = article_text(page);
+        if text.contains("That's the right answer") {
+            Ok(Submission::Correct)
+        } else if text.contains("That's not the right answer") {
+            let rejection = if text.contains("too high") {
+                Rejection::TooHigh
+            } else if text.contains("too low") {
+                Rejection::TooLow
+            } else {
+                Rejection::Wrong
+            };
+            Ok(Submission::Rejected(rejection))
+        } else if text.contains("You gave an answer too recently") {
+            Ok(Submission::Wait(parse_wait(&text)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Ok(Submission::WrongLevel)
+        } else {
+            Err(format!("unexpected response: {}", text))
+        }
+    }
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Submission::Correct => write!(f, "correct"),
+            Submission::Rejected(Rejection::Wrong) => write!(f, "wrong"),
+            Submission::Rejected(Rejection::TooLow) => write!(f, "wrong, too low"),
+            Submission::Rejected(Rejection::TooHigh) => write!(f, "wrong, too high"),
+            Submission::Wait(Some(wait)) => write!(f, "submitted too recently, wait {}s", wait.as_secs()),
+            Submission::Wait(None) => write!(f, "submitted too recently"),
+            Submission::WrongLevel => write!(f, "the part is solved already or locked"),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, or of the whole page if it has none, without tags
+fn article_text(page: &str) -> String {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, article)| article.split_once('>'))
+        .and_then(|(_, article)| article.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parses the time left to wait, e.g. from `You have 1m 4s left to wait.`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (_, wait) = text.split_once("You have ")?;
+    let (wait, _) = wait.split_once(" left to wait")?;
+    wait.split_whitespace().try_fold(Duration::ZERO, |total, value| {
+        let secs = match value.strip_suffix('m') {
+            Some(minutes) => minutes.parse::<u64>().ok()? * 60,
+            None => value.strip_suffix('s')?.parse::<u64>().ok()?,
+        };
+        Some(total + Duration::from_secs(secs))
+    })
+}
+
+/// A client of the Advent of Code website
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+    user_agent: Option<String>,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    /// Minimal time between two requests unless another one is given
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Creates a client sending `session` as session cookie to [DEFAULT_BASE_URL]
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            agent: ureq::Agent::new(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            session: session.into().trim().to_string(),
+            user_agent: None,
+            interval: Self::DEFAULT_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    /// Sends requests to `base_url` instead, e.g. a mirror or a local test server
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Identifies the client with `user_agent`, which should tell how to contact its user
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Waits at least `interval` between two requests
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sends a GET request to `path`, e.g. `/2025/day/1/input`, and returns the response's body
+    pub fn get(&mut self, path: &str) -> Result<String, String> {
+        let request = self.request("GET", path);
+        read_response(request.call())
+    }
+
+    /// Sends a POST request of a form to `path` and returns the response's body
+    pub fn post(&mut self, path: &str, form: &[(&str, &str)]) -> Result<String, String> {
+        let request = self.request("POST", path);
+        read_response(request.send_form(form))
+    }
+
+    /// Submits the `answer` of `part` of `day` of `year`
+    pub fn submit(&mut self, year: usize, day: usize, part: usize, answer: &str) -> Result<Submission, String> {
+        let level = part.to_string();
+        let page = self.post(&format!("/{}/day/{}/answer", year, day), &[("level", &level), ("answer", answer)])?;
+        Submission::parse(&page)
+    }
+
+    /// Downloads the input of `day` of `year` into `dir`, unless it is there already
+    pub fn fetch_input(&mut self, year: usize, day: usize, dir: &InputDir) -> Result<Fetched, String> {
+        self.fetch(&format!("/{}/day/{}/input", year, day), &dir.path(year, day))
+    }
+
+    /// Downloads the description of `day` of `year` to `<dir>/<year>/NN.html`, unless it is there
+    /// already
+    ///
+    /// Descriptions downloaded before solving part 1 lack part 2, which is not downloaded later.
+    pub fn fetch_puzzle(&mut self, year: usize, day: usize, dir: &Path) -> Result<Fetched, String> {
+        self.fetch(&format!("/{}/day/{}", year, day), &puzzle_path(dir, year, day))
+    }
+
+    /// Downloads `url_path` to the file at `path`, unless it exists
+    ///
+    /// The response is written to a temporary file first, so an interrupted download never leaves a
+    /// partial file behind.
+    fn fetch(&mut self, url_path: &str, path: &Path) -> Result<Fetched, String> {
+        if path.exists() {
+            return Ok(Fetched::Cached);
+        }
+
+        let body = self.get(url_path)?;
+        let partial = path.with_extension("part");
+        let written = fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&partial, body))
+            .and_then(|_| fs::rename(&partial, path));
+        written.map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(Fetched::Downloaded)
+    }
+
+    /// Creates a request to `path`, waiting until the interval since the last request passed
+    fn request(&mut self, method: &str, path: &str) -> ureq::Request {
+        if let Some(last_request) = self.last_request {
+            thread::sleep(self.interval.saturating_sub(last_request.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+
+        let mut request = self
+            .agent
+            .request(method, &format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", self.session));
+        if let Some(user_agent) = &self.user_agent {
+            request = request.set("User-Agent", user_agent);
+        }
+        request
+    }
+}
+
+/// Path of the description of `day` of `year` downloaded to `dir`, see [Client::fetch_puzzle]
+pub fn puzzle_path(dir: &Path, year: usize, day: usize) -> PathBuf {
+    dir.join(year.to_string()).join(format!("{:0>2}.html", day))
+}
+
+/// The body of a successful response, or a description of the failure
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, String> {
+    match response {
+        Ok(response) => {
+            let mut body = String::new();
+            response.into_reader().read_to_string(&mut body).map_err(|err| err.to_string())?;
+            Ok(body)
+        }
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            match body.lines().next().filter(|line| !line.is_empty()) {
+                Some(line) => Err(format!("HTTP {}: {}", status, line)),
+                None => Err(format!("HTTP {}", status)),
+            }
+        }
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    use super::*;
+
+    /// A request received by a [StubServer]: method, path, cookie and body
+    type Request = (String, String, String, String);
+
+    /// A local HTTP server answering each request with the status and body returned by `respond`
+    struct StubServer {
+        url: String,
+        requests: Arc<Mutex<Vec<Request>>>,
+    }
+
+    impl StubServer {
+        fn start(respond: impl Fn(&Request) -> (u16, String) + Send + 'static) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let requests = Arc::new(Mutex::new(vec![]));
+            let received = requests.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = stream.unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let mut parts = line.split_whitespace();
+                    let (method, path) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+
+                    let (mut cookie, mut len) = (String::new(), 0);
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        let Some((name, value)) = header.trim_end().split_once(": ") else {
+                            break;
+                        };
+                        match name.to_ascii_lowercase().as_str() {
+                            "cookie" => cookie = value.to_string(),
+                            "content-length" => len = value.parse().unwrap(),
+                            _ => {}
+                        }
+                    }
+                    let mut body = vec![0; len];
+                    reader.read_exact(&mut body).unwrap();
+
+                    let request = (method.to_string(), path.to_string(), cookie, String::from_utf8(body).unwrap());
+                    let (status, body) = respond(&request);
+                    received.lock().unwrap().push(request);
+                    write!(
+                        stream,
+                        "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        status,
+                        body.len(),
+                        body
+                    )
+                    .unwrap();
+                }
+            });
+            Self { url, requests }
+        }
+
+        /// Method and path of each request received so far
+        fn paths(&self) -> Vec<String> {
+            let requests = self.requests.lock().unwrap();
+            requests.iter().map(|(method, path, _, _)| format!("{} {}", method, path)).collect()
+        }
+    }
+
+    #[test]
+    fn fetch_and_cache_inputs() {
+        let server = StubServer::start(|(_, path, cookie, _)| match path.as_str() {
+            _ if cookie != "session=secret" => (400, "Puzzle inputs differ by user.".to_string()),
+            "/2025/day/1/input" => (200, "1\n2\n".to_string()),
+            "/2025/day/2/input" => (200, "3\n".to_string()),
+            "/2025/day/2" => (200, "<h2>--- Day 2: Gift Shop ---</h2>".to_string()),
+            _ => (404, "Not Found".to_string()),
+        });
+        let root = std::env::temp_dir().join(format!("aoc-runner-fetch-{}", std::process::id()));
+        let dir = InputDir::new(&root);
+        let interval = Duration::from_millis(50);
+        let mut client = Client::new("secret\n").with_base_url(format!("{}/", server.url)).with_interval(interval);
+
+        let start = Instant::now();
+        assert_eq!(client.fetch_input(2025, 1, &dir), Ok(Fetched::Downloaded));
+        assert_eq!(client.fetch_input(2025, 1, &dir), Ok(Fetched::Cached));
+        assert_eq!(client.fetch_input(2025, 2, &dir), Ok(Fetched::Downloaded));
+        assert_eq!(client.fetch_input(2025, 3, &dir), Err("HTTP 404: Not Found".to_string()));
+        // three requests, the cached input sent none
+        assert!(start.elapsed() >= 2 * interval);
+        assert_eq!(fs::read_to_string(dir.path(2025, 1)).unwrap(), "1\n2\n");
+        assert!(!dir.path(2025, 3).exists());
+        assert_eq!(client.fetch_puzzle(2025, 2, &root), Ok(Fetched::Downloaded));
+        assert_eq!(client.fetch_puzzle(2025, 2, &root), Ok(Fetched::Cached));
+        assert_eq!(fs::read_to_string(puzzle_path(&root, 2025, 2)).unwrap(), "<h2>--- Day 2: Gift Shop ---</h2>");
+
+        let mut stranger = Client::new("other").with_base_url(&server.url);
+        assert_eq!(stranger.fetch_input(2025, 4, &dir), Err("HTTP 400: Puzzle inputs differ by user.".to_string()));
+
+        assert_eq!(server.paths(), vec!["GET /2025/day/1/input", "GET /2025/day/2/input", "GET /2025/day/3/input", "GET /2025/day/2", "GET /2025/day/4/input"]);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn submit_answers() {
+        let server = StubServer::start(|(_, path, _, body)| {
+            let message = match body.as_str() {
+                _ if path != "/2025/day/3/answer" => "Not Found",
+                "level=1&answer=42" => "That's the right answer! You are one gold star closer.",
+                "level=2&answer=7" => "That's not the right answer; your answer is too low. Please wait one minute.",
+                "level=2&answer=9000" => "That's not the right answer; your answer is too high.",
+                "level=2&answer=a+b" => "That's not the right answer. If you're stuck, ...",
+                "level=2&answer=8" => "You gave an answer too recently; you have to wait. You have 1m 4s left to wait.",
+                _ => "You don't seem to be solving the right level. Did you already complete it?",
+            };
+            (200, format!("<html><main>\n<article><p>{}</p></article>\n</main></html>", message))
+        });
+        let mut client = Client::new("secret").with_base_url(&server.url).with_interval(Duration::ZERO);
+
+        assert_eq!(client.submit(2025, 3, 1, "42"), Ok(Submission::Correct));
+        assert_eq!(client.submit(2025, 3, 2, "7"), Ok(Submission::Rejected(Rejection::TooLow)));
+        assert_eq!(client.submit(2025, 3, 2, "9000"), Ok(Submission::Rejected(Rejection::TooHigh)));
+        assert_eq!(client.submit(2025, 3, 2, "a b"), Ok(Submission::Rejected(Rejection::Wrong)));
+        assert_eq!(client.submit(2025, 3, 2, "8"), Ok(Submission::Wait(Some(Duration::from_secs(64)))));
+        assert_eq!(client.submit(2025, 3, 1, "1"), Ok(Submission::WrongLevel));
+        assert_eq!(client.submit(2025, 4, 1, "1"), Err("unexpected response: Not Found".to_string()));
+        assert_eq!(server.paths()[0], "POST /2025/day/3/answer");
+    }
+}
@@ -0,0 +1,283 @@
+//! Analyzers made up of several analyzers, e.g. to measure run times and allocations at once
+//!
+//! Tuples, `Vec`s and `Option`s of analyzers and boxed analyzers are analyzers themselves. They
+//! forward each hook to all of their analyzers, which then each print their own report.
+//!
+//! `before_*` hooks are forwarded in order and `after_*` hooks in reverse order, so the first
+//! analyzer's measurements enclose the ones of the following analyzers. Put the analyzer whose
+//! measurements should be least affected by the others last, e.g. the [TimeAnalyzer][crate::TimeAnalyzer].
+
+use std::time::Duration;
+
+use crate::{Analyzer, Answer, Failure, InputDigest, ParallelAnalyzer, Phase, TimeAnalyzer, Trace};
+
+/// Several analyzers receiving the same hooks
+trait Composite {
+    /// Calls `hook` on each analyzer in order
+    fn each(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer));
+
+    /// Calls `hook` on each analyzer in reverse order
+    fn each_rev(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer));
+
+    /// The first [TimeAnalyzer] among the analyzers
+    fn find_time(&mut self) -> Option<&mut TimeAnalyzer>;
+}
+
+/// Implements [Analyzer] for a [Composite] by forwarding each hook to all of its analyzers, see the
+/// [module documentation][self]
+macro_rules! impl_composite_analyzer {
+    ([$($params:tt)*] $ty:ty) => {
+        impl<$($params)*> Analyzer for $ty {
+            fn before_all(&mut self) {
+                self.each(&mut |analyzer| analyzer.before_all());
+            }
+
+            fn after_all(&mut self) {
+                self.each_rev(&mut |analyzer| analyzer.after_all());
+            }
+
+            fn before_day(&mut self, day: usize) {
+                self.each(&mut |analyzer| analyzer.before_day(day));
+            }
+
+            fn after_day(&mut self, day: usize) {
+                self.each_rev(&mut |analyzer| analyzer.after_day(day));
+            }
+
+            fn before_parse(&mut self, day: usize) {
+                self.each(&mut |analyzer| analyzer.before_parse(day));
+            }
+
+            fn after_parse(&mut self, day: usize) {
+                self.each_rev(&mut |analyzer| analyzer.after_parse(day));
+            }
+
+            fn before_prepare(&mut self, day: usize) {
+                self.each(&mut |analyzer| analyzer.before_prepare(day));
+            }
+
+            fn after_prepare(&mut self, day: usize) {
+                self.each_rev(&mut |analyzer| analyzer.after_prepare(day));
+            }
+
+            fn before_part(&mut self, day: usize, part: usize) {
+                self.each(&mut |analyzer| analyzer.before_part(day, part));
+            }
+
+            fn after_part(&mut self, day: usize, part: usize) {
+                self.each_rev(&mut |analyzer| analyzer.after_part(day, part));
+            }
+
+            fn on_variant(&mut self, day: usize, part: usize, variant: &str) {
+                self.each(&mut |analyzer| analyzer.on_variant(day, part, variant));
+            }
+
+            fn on_trace(&mut self, day: usize, part: usize, trace: &Trace) {
+                self.each(&mut |analyzer| analyzer.on_trace(day, part, trace));
+            }
+
+            fn on_answer(&mut self, day: usize, part: usize, answer: &Answer) {
+                self.each(&mut |analyzer| analyzer.on_answer(day, part, answer));
+            }
+
+            fn on_input(&mut self, day: usize, input: &InputDigest) {
+                self.each(&mut |analyzer| analyzer.on_input(day, input));
+            }
+
+            fn on_failure(&mut self, day: usize, phase: Phase, failure: &Failure) {
+                self.each(&mut |analyzer| analyzer.on_failure(day, phase, failure));
+            }
+
+            fn on_timeout(&mut self, day: usize, part: usize, timeout: Duration) {
+                self.each(&mut |analyzer| analyzer.on_timeout(day, part, timeout));
+            }
+
+            fn time_analyzer(&mut self) -> Option<&mut TimeAnalyzer> {
+                self.find_time()
+            }
+        }
+    };
+}
+
+impl<A: Analyzer> Composite for Vec<A> {
+    fn each(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        for analyzer in self.iter_mut() {
+            hook(analyzer);
+        }
+    }
+
+    fn each_rev(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        for analyzer in self.iter_mut().rev() {
+            hook(analyzer);
+        }
+    }
+
+    fn find_time(&mut self) -> Option<&mut TimeAnalyzer> {
+        self.iter_mut().find_map(|analyzer| analyzer.time_analyzer())
+    }
+}
+
+impl_composite_analyzer!([A: Analyzer] Vec<A>);
+
+impl<A: ParallelAnalyzer> ParallelAnalyzer for Vec<A> {
+    fn fork(&self) -> Self {
+        self.iter().map(|analyzer| analyzer.fork()).collect()
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (analyzer, other) in self.iter_mut().zip(other) {
+            analyzer.merge(other);
+        }
+    }
+}
+
+/// An analyzer that may be disabled, e.g. depending on a command line flag
+impl<A: Analyzer> Composite for Option<A> {
+    fn each(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        if let Some(analyzer) = self {
+            hook(analyzer);
+        }
+    }
+
+    fn each_rev(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        self.each(hook);
+    }
+
+    fn find_time(&mut self) -> Option<&mut TimeAnalyzer> {
+        self.as_mut().and_then(|analyzer| analyzer.time_analyzer())
+    }
+}
+
+impl_composite_analyzer!([A: Analyzer] Option<A>);
+
+impl<A: ParallelAnalyzer> ParallelAnalyzer for Option<A> {
+    fn fork(&self) -> Self {
+        self.as_ref().map(|analyzer| analyzer.fork())
+    }
+
+    fn merge(&mut self, other: Self) {
+        if let (Some(analyzer), Some(other)) = (self, other) {
+            analyzer.merge(other);
+        }
+    }
+}
+
+impl Composite for Box<dyn Analyzer> {
+    fn each(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        hook(self.as_mut());
+    }
+
+    fn each_rev(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        self.each(hook);
+    }
+
+    fn find_time(&mut self) -> Option<&mut TimeAnalyzer> {
+        self.as_mut().time_analyzer()
+    }
+}
+
+impl_composite_analyzer!([] Box<dyn Analyzer>);
+
+impl Composite for Box<dyn Analyzer + Send> {
+    fn each(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        hook(self.as_mut());
+    }
+
+    fn each_rev(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+        self.each(hook);
+    }
+
+    fn find_time(&mut self) -> Option<&mut TimeAnalyzer> {
+        self.as_mut().time_analyzer()
+    }
+}
+
+impl_composite_analyzer!([] Box<dyn Analyzer + Send>);
+
+/// Implements [Analyzer] and [ParallelAnalyzer] for a tuple of analyzers
+macro_rules! impl_tuple {
+    ($($name:ident: $idx:tt),+; $($rev:tt),+) => {
+        impl<$($name: Analyzer),+> Composite for ($($name,)+) {
+            fn each(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+                $(hook(&mut self.$idx);)+
+            }
+
+            fn each_rev(&mut self, hook: &mut dyn FnMut(&mut dyn Analyzer)) {
+                $(hook(&mut self.$rev);)+
+            }
+
+            fn find_time(&mut self) -> Option<&mut TimeAnalyzer> {
+                $(
+                    if let Some(time) = self.$idx.time_analyzer() {
+                        return Some(time);
+                    }
+                )+
+                None
+            }
+        }
+
+        impl_composite_analyzer!([$($name: Analyzer),+] ($($name,)+));
+
+        impl<$($name: ParallelAnalyzer),+> ParallelAnalyzer for ($($name,)+) {
+            fn fork(&self) -> Self {
+                ($(self.$idx.fork(),)+)
+            }
+
+            fn merge(&mut self, other: Self) {
+                $(self.$idx.merge(other.$idx);)+
+            }
+        }
+    };
+}
+
+impl_tuple!(A: 0; 0);
+impl_tuple!(A: 0, B: 1; 1, 0);
+impl_tuple!(A: 0, B: 1, C: 2; 2, 1, 0);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3; 3, 2, 1, 0);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4; 4, 3, 2, 1, 0);
+impl_tuple!(A: 0, B: 1, C: 2, D: 3, E: 4, F: 5; 5, 4, 3, 2, 1, 0);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Records the days it saw, tagged with its name
+    struct Log(&'static str, std::rc::Rc<std::cell::RefCell<Vec<String>>>);
+
+    impl Analyzer for Log {
+        fn before_day(&mut self, day: usize) {
+            self.1.borrow_mut().push(format!("before {} {}", self.0, day));
+        }
+
+        fn after_day(&mut self, day: usize) {
+            self.1.borrow_mut().push(format!("after {} {}", self.0, day));
+        }
+    }
+
+    #[test]
+    fn forward_hooks_in_order() {
+        let log = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let mut analyzer = (
+            Log("a", log.clone()),
+            vec![Log("b", log.clone()), Log("c", log.clone())],
+            None::<Log>,
+            Box::new(Log("d", log.clone())) as Box<dyn Analyzer>,
+        );
+        analyzer.before_day(7);
+        analyzer.after_day(7);
+        assert_eq!(
+            *log.borrow(),
+            vec!["before a 7", "before b 7", "before c 7", "before d 7", "after d 7", "after c 7", "after b 7", "after a 7"]
+        );
+    }
+
+    #[test]
+    fn find_time_analyzer() {
+        let log = std::rc::Rc::new(std::cell::RefCell::new(vec![]));
+        let mut analyzer = (Log("a", log.clone()), None::<TimeAnalyzer>);
+        assert!(analyzer.time_analyzer().is_none());
+
+        let mut analyzer = (Log("a", log.clone()), vec![Box::new(TimeAnalyzer::new()) as Box<dyn Analyzer>]);
+        assert!(analyzer.time_analyzer().is_some());
+    }
+}
@@ -1,9 +1,72 @@
 //! Defines a common interface for [Advent of Code](http://adventofcode.com) puzzles
 
+use std::io::BufRead;
+
+use crate::{Answer, ParseError};
+
+/// An example input together with the expected answers of part 1 and part 2
+///
+/// An expected answer of `None` means the example does not apply to that part.
+pub type Example = (&'static str, Option<&'static str>, Option<&'static str>);
+
+/// A named alternative implementation of a part, e.g. `("naive", Self::part2_naive)`
+///
+/// Variants are selected with [RunOptions::variant][crate::RunOptions::variant] or all run and
+/// compared with [RunOptions::cross_check][crate::RunOptions::cross_check].
+pub type Variant<D, R> = (&'static str, fn(&mut D) -> R);
+
+/// Parses an input while it is read, see [STREAMING][Day::STREAMING]
+pub type StreamParser<D> = fn(&mut D, &mut dyn BufRead) -> Result<(), ParseError>;
+
+/// Name of the variant implemented by [Day::part1] and [Day::part2]
+pub const DEFAULT_VARIANT: &str = "default";
+
 /// A day's challenge
-pub trait Day: Default {
-    type Result1: std::fmt::Display + Sized;
-    type Result2: std::fmt::Display + Sized;
+///
+/// Days may borrow from the input they parse, e.g. hold `&'input str` slices instead of copies:
+/// ```ignore
+/// #[derive(Default)]
+/// pub struct Day11<'input> {
+///     devices: Vec<&'input str>,
+/// }
+///
+/// impl<'input> Day<'input> for Day11<'input> { ... }
+/// ```
+/// Days owning their parsed data implement `Day<'_>` for any input lifetime.
+pub trait Day<'input>: Default {
+    /// Answer type of part 1, e.g. an integer, a `String` or an [Answer]
+    type Result1: Into<Answer> + 'static;
+    /// Answer type of part 2, e.g. an integer, a `String` or an [Answer]
+    type Result2: Into<Answer> + 'static;
+
+    /// Example inputs from the puzzle description, see [Example]
+    const EXAMPLES: &'static [Example] = &[];
+
+    /// Parses streamed inputs while they are read instead of reading them into memory first, e.g.
+    /// `Some(Self::parse_stream)` for an input too large to keep in memory
+    ///
+    /// Inputs already in memory are still passed to [parse][Day::parse]. See
+    /// [numbered_lines][crate::numbered_lines].
+    const STREAMING: Option<StreamParser<Self>> = None;
+
+    /// Run each part on a freshly parsed instance, for days whose parts change the parsed state
+    ///
+    /// The runner parses the input into a fresh instance again before each further part or
+    /// variant, without measuring it. Streamed inputs are read into memory first.
+    const FRESH_PARTS: bool = false;
+
+    /// Measure [prepare][Day::prepare] as its own phase instead of counting it towards parsing
+    const PREPARE: bool = false;
+
+    /// Alternative implementations of part 1, see [Variant]
+    fn variants1() -> Vec<Variant<Self, Self::Result1>> {
+        vec![]
+    }
+
+    /// Alternative implementations of part 2, see [Variant]
+    fn variants2() -> Vec<Variant<Self, Self::Result2>> {
+        vec![]
+    }
 
     /// Part 1 of this day's challenge
     fn part1(&mut self) -> Self::Result1;
@@ -12,15 +75,33 @@ pub trait Day: Default {
     fn part2(&mut self) -> Self::Result2;
 
     /// Print result of part 1
-    fn print_part1(&self, result: Self::Result1) {
-        println!(" - Part 1: {}", result)
+    fn print_part1(&self, answer: &Answer) {
+        print_answer(1, answer)
     }
 
     /// Print result of part 2
-    fn print_part2(&self, result: Self::Result2) {
-        println!(" - Part 2: {}", result)
+    fn print_part2(&self, answer: &Answer) {
+        print_answer(2, answer)
     }
 
     /// Optional: parse input to use later in part1/part2
-    fn parse(&mut self, _input: &str) {}
+    ///
+    /// Malformed input should be reported as a [ParseError] instead of panicking.
+    fn parse(&mut self, _input: &'input str) -> Result<(), ParseError> {
+        Ok(())
+    }
+
+    /// Optional: compute state shared by part1 and part2 from the parsed input
+    ///
+    /// Called once after parsing, before running any part. Measured as its own phase if
+    /// [PREPARE][Day::PREPARE] is set, otherwise counted towards parsing.
+    fn prepare(&mut self) {}
+}
+
+/// Prints the answer of `part`, starting grids on their own line
+pub fn print_answer(part: usize, answer: &Answer) {
+    match answer {
+        Answer::Grid(_) => println!(" - Part {}:\n{}", part, answer),
+        _ => println!(" - Part {}: {}", part, answer),
+    }
 }
@@ -0,0 +1,145 @@
+//! Running a day's [examples][crate::Day::EXAMPLES] from the CLI or as unit tests
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::{
+    answers::Verdict,
+    day::{Variant, DEFAULT_VARIANT},
+    runner::panic_message,
+    Answer, Day, ParseError,
+};
+
+/// Runs the `variant` of `part` of a fresh `D` on an example's `input` and returns its answer
+fn solve<D: Day<'static>>(input: &'static str, part: usize, variant: &str) -> Result<Answer, ParseError> {
+    fn find<D, R>(default: fn(&mut D) -> R, variants: Vec<Variant<D, R>>, variant: &str) -> fn(&mut D) -> R {
+        variants
+            .into_iter()
+            .find(|(name, _)| *name == variant)
+            .map_or(default, |(_, solve)| solve)
+    }
+
+    let mut day = D::default();
+    day.parse(input)?;
+    day.prepare();
+    let answer = match part {
+        1 => find(D::part1, D::variants1(), variant)(&mut day).into(),
+        2 => find(D::part2, D::variants2(), variant)(&mut day).into(),
+        part => panic!("Invalid part: {}. Valid parts are: 1,2", part),
+    };
+    Ok(answer)
+}
+
+/// Names of all implementations of `part`, the default one first
+fn variant_names<D: Day<'static>>(part: usize) -> Vec<&'static str> {
+    let variants = match part {
+        1 => D::variants1().into_iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        _ => D::variants2().into_iter().map(|(name, _)| name).collect(),
+    };
+    [DEFAULT_VARIANT].into_iter().chain(variants).collect()
+}
+
+/// The expected answer of `part` for `example`
+fn expected(example: &crate::day::Example, part: usize) -> Option<&'static str> {
+    match part {
+        1 => example.1,
+        _ => example.2,
+    }
+}
+
+/// Runs all examples of `D`, prints their verdicts and returns true if all of them passed
+///
+/// An example whose parse or part panics fails like a part of a regular run does, without
+/// stopping the remaining examples.
+pub fn run_examples<D: Day<'static>>(day: usize) -> bool {
+    println!("Day {}", day);
+    if D::EXAMPLES.is_empty() {
+        println!(" - No examples");
+    }
+
+    let mut passed = true;
+    for (idx, example) in D::EXAMPLES.iter().enumerate() {
+        for part in [1, 2] {
+            let Some(expected) = expected(example, part) else {
+                continue;
+            };
+
+            match catch_unwind(AssertUnwindSafe(|| solve::<D>(example.0, part, DEFAULT_VARIANT))) {
+                Ok(Ok(answer)) => {
+                    let verdict = Verdict::check(Some(expected), &answer);
+                    passed &= verdict == Verdict::Pass;
+                    match &verdict {
+                        Verdict::Fail { expected, .. } => println!(
+                            " - Example {}, part {}: {} ({}, expected {})",
+                            idx + 1, part, answer, verdict, expected
+                        ),
+                        _ => println!(" - Example {}, part {}: {} ({})", idx + 1, part, answer, verdict),
+                    }
+                }
+                Ok(Err(err)) => {
+                    passed = false;
+                    println!(" - Example {}, part {}: could not parse input, {:#}", idx + 1, part, err);
+                }
+                Err(payload) => {
+                    passed = false;
+                    println!(" - Example {}, part {}: FAILED ({})", idx + 1, part, panic_message(payload.as_ref()));
+                }
+            }
+        }
+    }
+
+    passed
+}
+
+/// Asserts that all examples of `D` for `part` produce their expected answer with each variant
+///
+/// Used by [example_tests][crate::example_tests].
+pub fn assert_examples<D: Day<'static>>(part: usize) {
+    let examples = D::EXAMPLES
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, example)| Some((idx, example.0, expected(example, part)?)))
+        .collect::<Vec<_>>();
+    assert!(!examples.is_empty(), "No examples for part {}", part);
+
+    for (idx, input, expected) in examples {
+        for variant in variant_names::<D>(part) {
+            let answer = solve::<D>(input, part, variant)
+                .unwrap_or_else(|err| panic!("Example {}: could not parse input, {:#}", idx + 1, err));
+            assert!(
+                answer.matches(expected),
+                "Example {}, part {} ({}): expected {}, got {}",
+                idx + 1,
+                part,
+                variant,
+                expected,
+                answer
+            );
+        }
+    }
+}
+
+/// Generates the unit tests `part_1` and `part_2` from a day's [examples][crate::Day::EXAMPLES]
+///
+/// # Example
+/// ```ignore
+/// #[cfg(test)]
+/// mod test {
+///     use super::*;
+///
+///     aoc_runner::example_tests!(Day01);
+/// }
+/// ```
+#[macro_export]
+macro_rules! example_tests {
+    ($day: ty) => {
+        #[test]
+        fn part_1() {
+            $crate::examples::assert_examples::<$day>(1);
+        }
+
+        #[test]
+        fn part_2() {
+            $crate::examples::assert_examples::<$day>(2);
+        }
+    };
+}
@@ -0,0 +1,262 @@
+//! History of measured run times, to compare a run against earlier ones
+
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{
+    report::{csv_field, Record, Status},
+    InputDigest,
+};
+
+/// A file holding the [records][Record] of earlier runs, one CSV line per record
+///
+/// Each run is identified by the time it was saved at, in nanoseconds since the Unix epoch, and
+/// each line also holds the [profile] the runner was built with.
+pub struct History {
+    path: PathBuf,
+}
+
+/// The records of a single earlier run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub id: String,
+    /// Build profile of the run, see [profile]
+    pub profile: String,
+    pub records: Vec<Record>,
+}
+
+/// The profile the runner was built with, `debug` or `release`
+pub fn profile() -> &'static str {
+    if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    }
+}
+
+impl History {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Reads all runs in the order they were saved. A missing file holds no runs
+    pub fn load(&self) -> std::io::Result<Vec<Run>> {
+        let content = match fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err),
+        };
+
+        let mut runs: Vec<Run> = vec![];
+        for fields in parse_csv(&content).into_iter().skip(1) {
+            let Some((id, profile, record)) = parse_record(&fields) else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some(run) if run.id == id => run.records.push(record),
+                _ => runs.push(Run { id, profile, records: vec![record] }),
+            }
+        }
+        Ok(runs)
+    }
+
+    /// Appends `records` as a new run and returns its id
+    pub fn save(&self, records: &[Record]) -> std::io::Result<String> {
+        let id = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos().to_string();
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let is_new = !self.path.exists();
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        if is_new {
+            writeln!(file, "run,profile,day,phase,variant,status,time_ns,answer,input_len,input_hash")?;
+        }
+        for record in records {
+            let (len, hash) = match record.input {
+                Some(input) => (input.len.to_string(), format!("{:016x}", input.hash)),
+                None => Default::default(),
+            };
+            writeln!(
+                file,
+                "{},{},{},{},{},{},{},{},{},{}",
+                id,
+                profile(),
+                record.day,
+                record.phase,
+                csv_field(&record.variant),
+                record.status,
+                record.time.map(|time| time.as_nanos().to_string()).unwrap_or_default(),
+                record.answer.as_deref().map(csv_field).unwrap_or_default(),
+                len,
+                hash,
+            )?;
+        }
+        Ok(id)
+    }
+}
+
+/// Run times of an earlier run to compare against
+///
+/// Keyed by day, phase and variant of the [records][Record], along with the input they were
+/// measured on.
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    /// Describes the compared runs, e.g. `run 1733011200000000000`
+    pub description: String,
+    times: HashMap<(usize, String, String), (Duration, Option<InputDigest>)>,
+}
+
+impl Baseline {
+    /// The most recent run time of each phase in `runs`, which may stem from different runs
+    ///
+    /// Only runs built with the same [profile] as this one are taken into account.
+    pub fn latest(runs: &[Run]) -> Self {
+        let mut baseline = Self {
+            description: format!("previous {} runs", profile()),
+            ..Default::default()
+        };
+        for run in runs.iter().filter(|run| run.profile == profile()) {
+            baseline.extend(run);
+        }
+        baseline
+    }
+
+    /// The run times of the run with the given `id`
+    pub fn of(runs: &[Run], id: &str) -> Option<Self> {
+        let run = runs.iter().find(|run| run.id == id)?;
+        let mut baseline = Self {
+            description: format!("run {}", id),
+            ..Default::default()
+        };
+        baseline.extend(run);
+        Some(baseline)
+    }
+
+    fn extend(&mut self, run: &Run) {
+        for record in run.records.iter().filter(|record| record.status == Status::Ok) {
+            if let Some(time) = record.time {
+                let key = (record.day, record.phase.to_string(), record.variant.clone());
+                self.times.insert(key, (time, record.input));
+            }
+        }
+    }
+
+    /// The baseline's run time of a phase, if it has one that was measured on the same `input`
+    ///
+    /// Times of unknown inputs are compared to all inputs.
+    pub fn get(&self, day: usize, phase: &str, variant: &str, input: Option<&InputDigest>) -> Option<Duration> {
+        let (time, base_input) = self.times.get(&(day, phase.to_string(), variant.to_string()))?;
+        match (base_input, input) {
+            (Some(base_input), Some(input)) if base_input != input => None,
+            _ => Some(*time),
+        }
+    }
+}
+
+/// Parses a line of the history into the id and profile of its run and its record
+fn parse_record(fields: &[String]) -> Option<(String, String, Record)> {
+    let [id, profile, day, phase, variant, status, time, answer, len, hash] = fields else {
+        return None;
+    };
+    let record = Record {
+        day: day.parse().ok()?,
+        phase: match phase.as_str() {
+            "parse" => "parse",
+            "prepare" => "prepare",
+            "part1" => "part1",
+            "part2" => "part2",
+            "day" => "day",
+            _ => return None,
+        },
+        variant: variant.clone(),
+        status: status.parse().ok()?,
+        time: match time.as_str() {
+            "" => None,
+            time => Some(Duration::from_nanos(time.parse().ok()?)),
+        },
+        answer: Some(answer.clone()).filter(|answer| !answer.is_empty()),
+        stats: None,
+        input: match (len.as_str(), hash.as_str()) {
+            ("", "") => None,
+            (len, hash) => Some(InputDigest {
+                len: len.parse().ok()?,
+                hash: u64::from_str_radix(hash, 16).ok()?,
+            }),
+        },
+    };
+    Some((id.clone(), profile.clone(), record))
+}
+
+/// Splits CSV content into lines of fields, which may be quoted and then contain line breaks
+fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut lines = vec![];
+    let mut fields = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = content.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => quoted = !quoted,
+            ',' if !quoted => fields.push(std::mem::take(&mut field)),
+            '\n' if !quoted => {
+                fields.push(std::mem::take(&mut field));
+                lines.push(std::mem::take(&mut fields));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        lines.push(fields);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn save_and_load_runs() {
+        let path = std::env::temp_dir().join(format!("aoc-runner-history-{}.csv", std::process::id()));
+        let history = History::new(&path);
+        let input = InputDigest::of(b"1,2\n");
+        let record = |phase, time, answer: Option<&str>| Record {
+            day: 9,
+            phase,
+            variant: "default".to_string(),
+            status: Status::Ok,
+            time: Some(Duration::from_nanos(time)),
+            answer: answer.map(str::to_string),
+            stats: None,
+            input: Some(input),
+        };
+        let records = vec![record("part1", 100, Some("a,\"b\"\n#.")), Record { input: None, ..record("part2", 200, None) }];
+
+        let first = history.save(&records).unwrap();
+        let second = history.save(&records[..1]).unwrap();
+        let runs = history.load().unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(runs, vec![
+            Run { id: first, profile: profile().to_string(), records: records.clone() },
+            Run { id: second, profile: profile().to_string(), records: records[..1].to_vec() },
+        ]);
+        let baseline = Baseline::latest(&runs);
+        assert_eq!(baseline.get(9, "part1", "default", Some(&input)), Some(Duration::from_nanos(100)));
+        assert_eq!(baseline.get(9, "part1", "default", Some(&InputDigest::of(b"3,4\n"))), None);
+        assert_eq!(baseline.get(9, "part2", "default", Some(&input)), Some(Duration::from_nanos(200)));
+        assert_eq!(baseline.get(9, "parse", "default", None), None);
+    }
+}
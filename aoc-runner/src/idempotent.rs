@@ -0,0 +1,172 @@
+//! Checks that a day gives the same answers however often and in whichever order its phases run
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use crate::{runner::panic_message, Answer, Day};
+
+/// A way of running a day's phases on a single instance
+struct Scenario {
+    name: &'static str,
+    /// Number of times the input is parsed before running any part
+    parses: usize,
+    /// Parts in the order they run
+    parts: &'static [usize],
+}
+
+const SCENARIOS: [Scenario; 3] = [
+    Scenario { name: "parse twice", parses: 2, parts: &[1, 2] },
+    Scenario { name: "part 1 first", parses: 1, parts: &[1, 1, 2, 2] },
+    Scenario { name: "part 2 first", parses: 1, parts: &[2, 2, 1, 1] },
+];
+
+impl Scenario {
+    /// Tells how the runner avoids running `D` like this, if it does
+    fn avoided<'input, D: Day<'input>>(&self) -> Option<&'static str> {
+        if self.parses > 1 {
+            Some("the runner parses into a fresh instance")
+        } else if D::FRESH_PARTS {
+            Some("parts run on fresh instances")
+        } else {
+            None
+        }
+    }
+}
+
+/// The answer of a part, or the message it panicked with
+type Outcome = Result<Answer, String>;
+
+/// Runs `part` of `day`, catching panics
+fn solve<'input, D: Day<'input>>(day: &mut D, part: usize) -> Outcome {
+    let answer = catch_unwind(AssertUnwindSafe(|| match part {
+        1 => day.part1().into(),
+        _ => day.part2().into(),
+    }));
+    answer.map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// Parses `input` into `day`, catching panics. Fails with a description of why it did not parse
+fn parse<'input, D: Day<'input>>(day: &mut D, input: &'input str) -> Result<(), String> {
+    match catch_unwind(AssertUnwindSafe(|| day.parse(input))) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(err)) => Err(format!("could not parse input, {:#}", err)),
+        Err(payload) => Err(format!("panicked while parsing ({})", panic_message(payload.as_ref()))),
+    }
+}
+
+/// Describes an outcome on a single line
+fn describe(outcome: &Outcome) -> String {
+    match outcome {
+        Ok(Answer::Grid(_)) => "a grid".to_string(),
+        Ok(answer) => answer.to_string(),
+        Err(message) => format!("a panic ({})", message),
+    }
+}
+
+/// Runs the phases of `D` on `input` in several orders and compares the answers of each part to
+/// the ones of a freshly parsed instance
+///
+/// Prints each inconsistency and returns true if there are none, ignoring the ones the runner
+/// avoids, e.g. for days setting [FRESH_PARTS][Day::FRESH_PARTS].
+pub fn check_idempotent<'input, D: Day<'input>>(day_no: usize, input: &'input str) -> bool {
+    println!("Day {}", day_no);
+
+    let mut expected = vec![];
+    for part in [1, 2] {
+        let mut day = D::default();
+        if let Err(message) = parse(&mut day, input) {
+            println!(" - {}", message);
+            return false;
+        }
+        day.prepare();
+        expected.push(solve(&mut day, part));
+    }
+
+    let mut consistent = true;
+    let mut inconsistencies = 0;
+    'scenarios: for scenario in SCENARIOS.iter() {
+        let mut day = D::default();
+        for run in 0..scenario.parses {
+            // the input parsed into a fresh instance before
+            if let Err(message) = parse(&mut day, input) {
+                inconsistencies += 1;
+                let avoided = scenario.avoided::<D>();
+                consistent &= avoided.is_some();
+                println!(
+                    " - {}: parse {}: {}{}",
+                    scenario.name,
+                    run + 1,
+                    message,
+                    avoided.map(|avoided| format!(" ({})", avoided)).unwrap_or_default(),
+                );
+                continue 'scenarios;
+            }
+        }
+        day.prepare();
+        for (run, &part) in scenario.parts.iter().enumerate() {
+            let outcome = solve(&mut day, part);
+            if outcome == expected[part - 1] {
+                continue;
+            }
+
+            inconsistencies += 1;
+            let avoided = scenario.avoided::<D>();
+            consistent &= avoided.is_some();
+            println!(
+                " - {}: run {} of part {} gave {}, expected {}{}",
+                scenario.name,
+                run + 1,
+                part,
+                describe(&outcome),
+                describe(&expected[part - 1]),
+                avoided.map(|avoided| format!(" ({})", avoided)).unwrap_or_default(),
+            );
+        }
+    }
+    if inconsistencies == 0 {
+        println!(" - consistent");
+    }
+
+    consistent
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_days::Draining;
+
+    #[test]
+    fn detect_inconsistent_answers() {
+        assert!(!check_idempotent::<Draining<false>>(1, "abc"));
+    }
+
+    /// A day that panics when parsing its input again
+    #[derive(Default)]
+    struct Parsed(usize);
+
+    impl<'input> Day<'input> for Parsed {
+        type Result1 = usize;
+        type Result2 = usize;
+
+        fn parse(&mut self, _input: &'input str) -> Result<(), crate::ParseError> {
+            if self.0 > 0 {
+                panic!("parsed twice");
+            }
+            self.0 += 1;
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Self::Result1 {
+            self.0
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn report_panics_while_parsing() {
+        // the runner never parses twice into the same instance
+        assert!(check_idempotent::<Parsed>(1, ""));
+    }
+}
@@ -0,0 +1,154 @@
+//! A puzzle's input, either already in memory or read while parsing
+
+use std::{
+    fmt::Display,
+    io::{BufRead, Read},
+};
+
+/// A puzzle's input
+///
+/// Days that parse [streaming][crate::Day::STREAMING] read a [Stream][Input::Stream] while parsing
+/// it, all other days get it read into memory first.
+pub enum Input<'input> {
+    /// An input already in memory
+    Text(&'input str),
+    /// An input read while parsing, e.g. from stdin or a file
+    Stream(Box<dyn BufRead + Send + 'input>),
+}
+
+impl<'input> Input<'input> {
+    /// Creates a streamed input reading from `reader`
+    pub fn stream(reader: impl BufRead + Send + 'input) -> Self {
+        Self::Stream(Box::new(reader))
+    }
+
+    /// Returns the whole input as text, reading a stream to its end
+    pub fn into_string(self) -> std::io::Result<String> {
+        match self {
+            Self::Text(text) => Ok(text.to_string()),
+            Self::Stream(mut reader) => {
+                let mut text = String::new();
+                reader.read_to_string(&mut text)?;
+                Ok(text)
+            }
+        }
+    }
+}
+
+impl<'input> From<&'input str> for Input<'input> {
+    fn from(text: &'input str) -> Self {
+        Self::Text(text)
+    }
+}
+
+/// Size and hash of a puzzle's input, e.g. to tell inputs apart or to key a cache
+///
+/// The hash is the 64-bit FNV-1a hash of the input's bytes, which is the same on all platforms and
+/// runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InputDigest {
+    /// Size in bytes
+    pub len: usize,
+    pub hash: u64,
+}
+
+impl InputDigest {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    /// The digest of `bytes`
+    pub fn of(bytes: &[u8]) -> Self {
+        let mut digest = Self::default();
+        digest.update(bytes);
+        digest
+    }
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.len += bytes.len();
+        for byte in bytes {
+            self.hash = (self.hash ^ *byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+}
+
+impl Default for InputDigest {
+    /// The digest of an empty input
+    fn default() -> Self {
+        Self { len: 0, hash: Self::OFFSET_BASIS }
+    }
+}
+
+impl Display for InputDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bytes, hash {:016x}", self.len, self.hash)
+    }
+}
+
+/// A reader computing the [digest][InputDigest] of all bytes read from `inner`
+pub(crate) struct DigestReader<R> {
+    inner: R,
+    digest: InputDigest,
+}
+
+impl<R: BufRead> DigestReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        Self { inner, digest: InputDigest::default() }
+    }
+
+    /// Reads the rest of the input and returns the digest of the whole input
+    pub(crate) fn finish(mut self) -> std::io::Result<InputDigest> {
+        std::io::copy(&mut self, &mut std::io::sink())?;
+        Ok(self.digest)
+    }
+}
+
+impl<R: BufRead> Read for DigestReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.inner.read(buf)?;
+        self.digest.update(&buf[..len]);
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for DigestReader<R> {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        // the buffer filled before consuming is returned again without reading
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.digest.update(&buf[..amt.min(buf.len())]);
+        }
+        self.inner.consume(amt);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn read_stream_into_text() {
+        let input = Input::stream("1\n2\n".as_bytes());
+        assert_eq!(input.into_string().unwrap(), "1\n2\n");
+    }
+
+    #[test]
+    fn digest_streamed_input() {
+        assert_eq!(InputDigest::of(b"").hash, 0xcbf29ce484222325);
+        assert_eq!(InputDigest::of(b"a").hash, 0xaf63dc4c8601ec8c);
+
+        let text = "first line\nsecond line\n";
+        let mut reader = DigestReader::new(std::io::BufReader::with_capacity(4, text.as_bytes()));
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        let mut rest = vec![];
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(reader.finish().unwrap(), InputDigest::of(text.as_bytes()));
+
+        let mut reader = DigestReader::new(std::io::BufReader::with_capacity(4, text.as_bytes()));
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(reader.finish().unwrap(), InputDigest::of(text.as_bytes()));
+    }
+}
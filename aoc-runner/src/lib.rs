@@ -1,7 +1,48 @@
 /// Scaffold methods for an AoC runner
 
+pub mod alloc;
 pub mod analyzer;
+pub mod answer;
+pub mod answers;
+pub mod build;
+pub mod cancel;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod compose;
 pub mod day;
+pub mod examples;
+pub mod history;
+pub mod idempotent;
+pub mod input;
+pub mod log;
+pub mod parse;
+pub mod provider;
+pub mod registry;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod stats;
+#[cfg(test)]
+mod test_days;
+pub mod trace;
+pub mod verify;
 
-pub use analyzer::{Analyzer, TimeAnalyzer};
-pub use day::Day;
+pub use alloc::{AllocAnalyzer, AllocStats, CountingAllocator};
+pub use analyzer::{Analyzer, Failure, ParallelAnalyzer, Phase, TimeAnalyzer};
+pub use answer::Answer;
+pub use answers::{AnswerStore, Answers, Rejected, Rejection, Verdict};
+pub use cancel::CancellationToken;
+pub use day::{Day, Example, StreamParser, Variant};
+pub use examples::run_examples;
+pub use history::{Baseline, History};
+pub use idempotent::check_idempotent;
+pub use input::{Input, InputDigest};
+pub use log::LogAnalyzer;
+pub use parse::{numbered_lines, parse_at, parse_in_line, ParseError, ParseResult};
+pub use provider::{Embedded, InMemory, InputDir, InputFile, InputProvider, Stdin};
+pub use registry::{Calendar, Puzzle, Registry};
+pub use report::{Report, ReportFormat};
+pub use runner::RunOptions;
+pub use stats::Stats;
+pub use trace::{Counter, ProgressAnalyzer, Trace, TraceAnalyzer, Tracer};
+pub use verify::Verifier;
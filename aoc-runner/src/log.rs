@@ -0,0 +1,112 @@
+//! Logging of each hook, to follow a run as it happens
+
+use std::time::{Duration, Instant};
+
+use crate::{Analyzer, Answer, Failure, InputDigest, ParallelAnalyzer, Phase, Trace};
+
+/// An analyzer printing each hook to stderr, prefixed with the time elapsed since the start of the run
+///
+/// Meant to be combined with other analyzers, see [compose][crate::compose].
+#[derive(Debug, Clone, Copy)]
+pub struct LogAnalyzer {
+    start: Instant,
+}
+
+impl LogAnalyzer {
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+
+    fn log(&self, message: std::fmt::Arguments) {
+        let elapsed = self.start.elapsed();
+        eprintln!("[{:>4}.{:03}s] {}", elapsed.as_secs(), elapsed.subsec_millis(), message);
+    }
+}
+
+impl Default for LogAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Analyzer for LogAnalyzer {
+    fn before_all(&mut self) {
+        self.start = Instant::now();
+        self.log(format_args!("start"));
+    }
+
+    fn after_all(&mut self) {
+        self.log(format_args!("done"));
+    }
+
+    fn before_day(&mut self, day: usize) {
+        self.log(format_args!("day {}: start", day));
+    }
+
+    fn after_day(&mut self, day: usize) {
+        self.log(format_args!("day {}: done", day));
+    }
+
+    fn before_parse(&mut self, day: usize) {
+        self.log(format_args!("day {}: parsing", day));
+    }
+
+    fn after_parse(&mut self, day: usize) {
+        self.log(format_args!("day {}: parsed", day));
+    }
+
+    fn before_prepare(&mut self, day: usize) {
+        self.log(format_args!("day {}: preparing", day));
+    }
+
+    fn after_prepare(&mut self, day: usize) {
+        self.log(format_args!("day {}: prepared", day));
+    }
+
+    fn on_input(&mut self, day: usize, input: &InputDigest) {
+        self.log(format_args!("day {}: input of {}", day, input));
+    }
+
+    fn before_part(&mut self, day: usize, part: usize) {
+        self.log(format_args!("day {}: running part {}", day, part));
+    }
+
+    fn after_part(&mut self, day: usize, part: usize) {
+        self.log(format_args!("day {}: finished part {}", day, part));
+    }
+
+    fn on_variant(&mut self, day: usize, part: usize, variant: &str) {
+        self.log(format_args!("day {}: cross-checking part {} with variant {}", day, part, variant));
+    }
+
+    fn on_trace(&mut self, day: usize, part: usize, trace: &Trace) {
+        match trace {
+            Trace::Progress { done, total } => self.log(format_args!("day {}: part {} at {}/{}", day, part, done, total)),
+            Trace::Message(message) => self.log(format_args!("day {}: part {}: {}", day, part, message)),
+        }
+    }
+
+    fn on_answer(&mut self, day: usize, part: usize, answer: &Answer) {
+        let answer = answer.to_string();
+        match answer.lines().count() {
+            0 | 1 => self.log(format_args!("day {}: part {} answered {}", day, part, answer)),
+            lines => self.log(format_args!("day {}: part {} answered {} lines", day, part, lines)),
+        }
+    }
+
+    fn on_failure(&mut self, day: usize, phase: Phase, failure: &Failure) {
+        self.log(format_args!("day {}: {} failed: {}", day, phase, failure));
+    }
+
+    fn on_timeout(&mut self, day: usize, part: usize, timeout: Duration) {
+        self.log(format_args!("day {}: part {} timed out after {:?}", day, part, timeout));
+    }
+}
+
+impl ParallelAnalyzer for LogAnalyzer {
+    fn fork(&self) -> Self {
+        *self
+    }
+
+    fn merge(&mut self, _other: Self) {}
+}
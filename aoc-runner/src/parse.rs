@@ -0,0 +1,168 @@
+//! Error type for failures while parsing a puzzle's input
+
+use std::{fmt::Display, io::BufRead, str::FromStr};
+
+/// Result of parsing a puzzle's input
+pub type ParseResult<T = ()> = Result<T, ParseError>;
+
+/// An error that occurred while parsing a puzzle's input
+///
+/// Carries the position of the offending text inside the input, so the runner can point at the
+/// exact line that broke instead of panicking somewhere inside a day's `parse`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the offending text (1-based)
+    pub line: usize,
+    /// Column of the offending text (1-based, counted in chars)
+    pub column: usize,
+    /// The offending text
+    pub text: String,
+    /// What was expected instead of the offending text
+    pub expected: String,
+    /// The whole line containing the offending text
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// Creates an error for `fragment`, which must be a slice of `input`
+    ///
+    /// Line and column are derived from the position of `fragment` inside `input`. If `fragment`
+    /// does not point into `input`, its first occurrence is used instead.
+    pub fn at(input: &str, fragment: &str, expected: impl Into<String>) -> Self {
+        let input_start = input.as_ptr() as usize;
+        let fragment_start = fragment.as_ptr() as usize;
+        let offset = if (input_start..=input_start + input.len()).contains(&fragment_start) {
+            fragment_start - input_start
+        } else {
+            input.find(fragment).unwrap_or(input.len())
+        };
+
+        let line_start = input[..offset].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+        let line_end = input[offset..].find('\n').map(|idx| offset + idx).unwrap_or(input.len());
+
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: fragment.to_string(),
+            expected: expected.into(),
+            source_line: input[line_start..line_end].trim_end_matches('\r').to_string(),
+        }
+    }
+
+    /// Creates an error pointing at the end of `input`
+    pub fn eof(input: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], expected)
+    }
+
+    /// Creates an error for `fragment`, which must be a slice of `line`, the `line_no`th line (1-based) of a
+    /// streamed input
+    pub fn in_line(line_no: usize, line: &str, fragment: &str, expected: impl Into<String>) -> Self {
+        Self { line: line_no, ..Self::at(line, fragment, expected) }
+    }
+
+    /// Creates an error for the `line_no`th line (1-based) of a streamed input that could not be read
+    pub fn io(line_no: usize, err: &std::io::Error) -> Self {
+        Self {
+            line: line_no,
+            column: 1,
+            text: String::new(),
+            expected: format!("readable input ({})", err),
+            source_line: String::new(),
+        }
+    }
+}
+
+/// Parses `fragment`, which must be a slice of `input`, into a `T`
+///
+/// On failure the returned [ParseError] points at `fragment` and names `expected`.
+pub fn parse_at<T: FromStr>(input: &str, fragment: &str, expected: &str) -> ParseResult<T> {
+    fragment.parse().map_err(|_| ParseError::at(input, fragment, expected))
+}
+
+/// Parses `fragment`, which must be a slice of `line`, the `line_no`th line (1-based) of a streamed input,
+/// into a `T`
+///
+/// On failure the returned [ParseError] points at `fragment` and names `expected`.
+pub fn parse_in_line<T: FromStr>(line_no: usize, line: &str, fragment: &str, expected: &str) -> ParseResult<T> {
+    fragment.parse().map_err(|_| ParseError::in_line(line_no, line, fragment, expected))
+}
+
+/// Reads `input` line by line, together with the number of each line (1-based)
+///
+/// Lines that cannot be read are reported as [ParseError::io].
+pub fn numbered_lines(input: &mut dyn BufRead) -> impl Iterator<Item = ParseResult<(usize, String)>> + '_ {
+    input.lines().enumerate().map(|(idx, line)| {
+        line.map(|line| (idx + 1, line)).map_err(|err| ParseError::io(idx + 1, &err))
+    })
+}
+
+/// Renders the error as a one-line message, or with the alternate flag (`{:#}`) followed by the
+/// offending line and a marker below the offending text
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: expected {}, ", self.line, self.column, self.expected)?;
+        if self.text.is_empty() {
+            write!(f, "found nothing")?;
+        } else {
+            write!(f, "found {:?}", self.text)?;
+        }
+
+        if f.alternate() {
+            let gutter = " ".repeat(self.line.to_string().len());
+            let marker = format!(
+                "{}{}",
+                " ".repeat(self.column - 1),
+                "^".repeat(self.text.chars().count().max(1))
+            );
+            write!(f, "\n{} |", gutter)?;
+            write!(f, "\n{} | {}", self.line, self.source_line)?;
+            write!(f, "\n{} | {}", gutter, marker)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn position_of_fragment() {
+        let input = "1-2\n3-x4\n5-6";
+        let fragment = &input[6..8];
+        let err = ParseError::at(input, fragment, "a number");
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.text, "x4");
+        assert_eq!(err.source_line, "3-x4");
+    }
+
+    #[test]
+    fn position_of_eof() {
+        let input = "1-2\n3-4";
+        let err = ParseError::eof(input, "a blank line");
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.to_string(), "line 2, column 4: expected a blank line, found nothing");
+    }
+
+    #[test]
+    fn position_in_streamed_line() {
+        let mut input = "1-2\n3-x4\n5-6".as_bytes();
+        let (line_no, line) = numbered_lines(&mut input).nth(1).unwrap().unwrap();
+        let err = ParseError::in_line(line_no, &line, &line[2..], "a number");
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.source_line, "3-x4");
+    }
+
+    #[test]
+    fn render_snippet() {
+        let input = "L68\nX30";
+        let err = parse_at::<i16>(input, &input[4..5], "'L' or 'R'").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "line 2, column 1: expected 'L' or 'R', found \"X\"\n  |\n2 | X30\n  | ^"
+        );
+    }
+}
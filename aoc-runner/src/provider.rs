@@ -0,0 +1,178 @@
+//! Where the runner, benchmarks and tests get the puzzles' inputs from
+
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufReader, Cursor, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::{registry::LAST_DAY, Input};
+
+/// Provides the input of each day of a year
+pub trait InputProvider {
+    /// The input of `day` of `year`, `None` if there is none
+    fn input(&self, year: usize, day: usize) -> std::io::Result<Option<Input<'static>>>;
+
+    /// The inputs of all days of `year`, see [Registry::run_some][crate::Registry::run_some]
+    fn inputs(&self, year: usize) -> std::io::Result<Vec<Option<Input<'static>>>> {
+        (1..=LAST_DAY).map(|day| self.input(year, day)).collect()
+    }
+
+    /// The input of `day` of `year` read into memory, e.g. for benchmarks
+    fn text(&self, year: usize, day: usize) -> std::io::Result<Option<String>> {
+        self.input(year, day)?.map(Input::into_string).transpose()
+    }
+}
+
+impl<P: InputProvider + ?Sized> InputProvider for &P {
+    fn input(&self, year: usize, day: usize) -> std::io::Result<Option<Input<'static>>> {
+        (**self).input(year, day)
+    }
+}
+
+impl<P: InputProvider + ?Sized> InputProvider for Box<P> {
+    fn input(&self, year: usize, day: usize) -> std::io::Result<Option<Input<'static>>> {
+        (**self).input(year, day)
+    }
+}
+
+/// Inputs stored in a directory as `<year>/NN.txt`, e.g. `./input/2025/01.txt`
+///
+/// Inputs are streamed, missing files are no input.
+#[derive(Debug, Clone)]
+pub struct InputDir {
+    root: PathBuf,
+}
+
+impl InputDir {
+    /// Directory of the inputs unless another one is given
+    pub const DEFAULT: &'static str = "./input";
+
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Path of the input of `day` of `year`
+    pub fn path(&self, year: usize, day: usize) -> PathBuf {
+        self.root.join(year.to_string()).join(format!("{:0>2}.txt", day))
+    }
+}
+
+impl Default for InputDir {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT)
+    }
+}
+
+impl InputProvider for InputDir {
+    fn input(&self, year: usize, day: usize) -> std::io::Result<Option<Input<'static>>> {
+        match File::open(self.path(year, day)) {
+            Ok(file) => Ok(Some(Input::stream(BufReader::new(file)))),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// A single file used as the input of any day
+#[derive(Debug, Clone)]
+pub struct InputFile {
+    path: PathBuf,
+}
+
+impl InputFile {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self { path: path.as_ref().to_path_buf() }
+    }
+}
+
+impl InputProvider for InputFile {
+    fn input(&self, _year: usize, _day: usize) -> std::io::Result<Option<Input<'static>>> {
+        let file = File::open(&self.path)?;
+        Ok(Some(Input::stream(BufReader::new(file))))
+    }
+}
+
+/// Stdin used as the input of any day, so it can only provide a single input
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Stdin;
+
+impl InputProvider for Stdin {
+    fn input(&self, _year: usize, _day: usize) -> std::io::Result<Option<Input<'static>>> {
+        Ok(Some(Input::stream(BufReader::new(std::io::stdin()))))
+    }
+}
+
+/// Inputs embedded into the binary at compile time
+///
+/// # Example
+/// ```ignore
+/// const INPUTS: Embedded = Embedded(&[(2025, 1, include_str!("../input/2025/01.txt"))]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Embedded(pub &'static [(usize, usize, &'static str)]);
+
+impl InputProvider for Embedded {
+    fn input(&self, year: usize, day: usize) -> std::io::Result<Option<Input<'static>>> {
+        let input = self.0.iter().find(|(y, d, _)| (*y, *d) == (year, day));
+        Ok(input.map(|(_, _, text)| Input::Text(text)))
+    }
+}
+
+/// Inputs held in memory, e.g. generated by tests
+///
+/// Each input is streamed from a copy, so it can be provided any number of times.
+#[derive(Debug, Clone, Default)]
+pub struct InMemory {
+    inputs: BTreeMap<(usize, usize), String>,
+}
+
+impl InMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the input of `day` of `year`, replacing the one it had
+    pub fn with(mut self, year: usize, day: usize, input: impl Into<String>) -> Self {
+        self.insert(year, day, input);
+        self
+    }
+
+    /// Adds the input of `day` of `year`, replacing the one it had
+    pub fn insert(&mut self, year: usize, day: usize, input: impl Into<String>) {
+        self.inputs.insert((year, day), input.into());
+    }
+}
+
+impl InputProvider for InMemory {
+    fn input(&self, year: usize, day: usize) -> std::io::Result<Option<Input<'static>>> {
+        let input = self.inputs.get(&(year, day)).cloned();
+        Ok(input.map(|input| Input::stream(Cursor::new(input))))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn provide_inputs() {
+        let memory = InMemory::new().with(2025, 2, "2\n");
+        let inputs = memory.inputs(2025).unwrap();
+        assert_eq!(inputs.len(), LAST_DAY);
+        assert!(inputs[0].is_none());
+        assert_eq!(memory.text(2025, 2).unwrap().as_deref(), Some("2\n"));
+        assert_eq!(memory.text(2025, 2).unwrap().as_deref(), Some("2\n"));
+        assert_eq!(memory.text(2024, 2).unwrap(), None);
+
+        let embedded = Embedded(&[(2025, 1, "1\n")]);
+        assert_eq!(embedded.text(2025, 1).unwrap().as_deref(), Some("1\n"));
+        assert_eq!(embedded.text(2025, 2).unwrap(), None);
+
+        let dir = InputDir::new("does-not-exist");
+        assert_eq!(dir.path(2025, 1), Path::new("does-not-exist/2025/01.txt"));
+        assert!(dir.input(2025, 1).unwrap().is_none());
+        assert!(InputFile::new("does-not-exist.txt").input(2025, 1).is_err());
+    }
+}
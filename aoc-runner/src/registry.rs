@@ -0,0 +1,462 @@
+//! Registry of all implemented days, keyed by their day number, and calendar of several years
+
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    sync::{Condvar, Mutex},
+};
+
+use crate::{runner, Analyzer, Day, Input, ParallelAnalyzer, RunOptions};
+
+/// The last day of an Advent of Code event
+pub const LAST_DAY: usize = 25;
+
+/// The year of the first Advent of Code event
+pub const FIRST_YEAR: usize = 2015;
+
+/// A day's puzzle together with its day number
+///
+/// Implemented by annotating the day with `#[aoc(day = N)]` from `derive_aoc_runner`.
+pub trait Puzzle: 'static {
+    /// The day number of this puzzle
+    const DAY: usize;
+
+    /// The day parsing an input it may borrow from for `'input`, e.g. `Day11<'input>`, or `Self` for
+    /// days owning their parsed data
+    type Day<'input>: Day<'input> + Send;
+}
+
+/// Type-erased interface of a registered [Puzzle]
+trait Entry: Send + Sync {
+    fn run(&self, part: Option<usize>, input: Input<'_>, options: &RunOptions, analyzer: &mut dyn Analyzer) -> bool;
+
+    fn run_buffered<'a>(
+        &self,
+        input: Input<'a>,
+        options: &RunOptions,
+        analyzer: &mut dyn Analyzer,
+        turn: Box<dyn FnOnce() + 'a>,
+    ) -> bool;
+
+    fn run_examples(&self) -> bool;
+
+    fn check_idempotent(&self, input: &str) -> bool;
+}
+
+/// A registered [Puzzle], which parses each input into a fresh instance of its day
+struct Registered<P>(PhantomData<fn() -> P>);
+
+impl<P: Puzzle> Entry for Registered<P> {
+    fn run(&self, part: Option<usize>, input: Input<'_>, options: &RunOptions, analyzer: &mut dyn Analyzer) -> bool {
+        runner::run_day::<P>(part, input, options, analyzer)
+    }
+
+    fn run_buffered<'a>(
+        &self,
+        input: Input<'a>,
+        options: &RunOptions,
+        analyzer: &mut dyn Analyzer,
+        turn: Box<dyn FnOnce() + 'a>,
+    ) -> bool {
+        runner::run_day_buffered::<P>(None, input, options, analyzer, turn)
+    }
+
+    fn run_examples(&self) -> bool {
+        crate::run_examples::<P::Day<'static>>(P::DAY)
+    }
+
+    fn check_idempotent(&self, input: &str) -> bool {
+        crate::check_idempotent::<P::Day<'_>>(P::DAY, input)
+    }
+}
+
+/// Lets days running at the same time print their output one after another, in order of days
+#[derive(Default)]
+struct Turns {
+    /// Index of the day whose turn it is
+    current: Mutex<usize>,
+    changed: Condvar,
+}
+
+impl Turns {
+    /// The turn of the `idx`th day
+    fn turn(&self, idx: usize) -> Turn<'_> {
+        Turn { turns: self, idx }
+    }
+}
+
+/// The turn of a single day, passed on to the next day once dropped
+struct Turn<'a> {
+    turns: &'a Turns,
+    idx: usize,
+}
+
+impl Turn<'_> {
+    /// Waits until it is this day's turn
+    fn wait(&self) {
+        let mut current = self.turns.current.lock().unwrap();
+        while *current < self.idx {
+            current = self.turns.changed.wait(current).unwrap();
+        }
+    }
+}
+
+impl Drop for Turn<'_> {
+    fn drop(&mut self) {
+        // a day failing before its turn must not pass on the turn of a day before it
+        self.wait();
+        *self.turns.current.lock().unwrap() = self.idx + 1;
+        self.turns.changed.notify_all();
+    }
+}
+
+/// All implemented days of an event
+///
+/// Days may be missing, running them lists them as not implemented. Each run parses the input into
+/// a fresh instance of the day, which may borrow from the input (see [Day]). A registry of all days
+/// annotated with `#[aoc(day = N)]` is usually generated by [collect_days][crate::build::collect_days].
+#[derive(Default)]
+pub struct Registry {
+    days: BTreeMap<usize, Box<dyn Entry>>,
+}
+
+impl Registry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `P` under its day number
+    ///
+    /// Panics if another puzzle is already registered for that day.
+    pub fn register<P: Puzzle>(&mut self) {
+        let previous = self.days.insert(P::DAY, Box::new(Registered::<P>(PhantomData)));
+        assert!(previous.is_none(), "Day {} is registered twice", P::DAY);
+    }
+
+    /// Return the numbers of all registered days in ascending order
+    pub fn days(&self) -> impl Iterator<Item = usize> + '_ {
+        self.days.keys().copied()
+    }
+
+    /// Return true if a puzzle is registered for `day`
+    pub fn contains(&self, day: usize) -> bool {
+        self.days.contains_key(&day)
+    }
+
+    /// Return the number of registered days
+    pub fn len(&self) -> usize {
+        self.days.len()
+    }
+
+    /// Return true if no day is registered
+    pub fn is_empty(&self) -> bool {
+        self.days.is_empty()
+    }
+
+    /// Runs both parts of a given day
+    ///
+    /// Returns true if all phases finished successfully.
+    pub fn run_day<'a>(
+        &self,
+        day: usize,
+        input: impl Into<Input<'a>>,
+        options: &RunOptions,
+        analyzer: &mut impl Analyzer,
+    ) -> bool {
+        self.run_part(day, None, input, options, analyzer)
+    }
+
+    /// Runs one or both parts of a given day
+    ///
+    /// Panics and parse errors are reported to the analyzer. Returns true if all phases finished
+    /// successfully, false if they did not or the day is not implemented.
+    pub fn run_part<'a>(
+        &self,
+        day: usize,
+        part: Option<usize>,
+        input: impl Into<Input<'a>>,
+        options: &RunOptions,
+        analyzer: &mut impl Analyzer,
+    ) -> bool {
+        match self.days.get(&day) {
+            Some(entry) => entry.run(part, input.into(), options, analyzer),
+            None => {
+                if !options.quiet {
+                    print_not_implemented(day);
+                }
+                false
+            }
+        }
+    }
+
+    /// Runs both parts for all days where an input is given, `inputs[0]` being the input of day 1
+    ///
+    /// Days with an input but without a puzzle are listed as not implemented. Returns true if all
+    /// phases of all implemented days finished successfully.
+    pub fn run_some(
+        &self,
+        inputs: Vec<Option<Input<'_>>>,
+        options: &RunOptions,
+        analyzer: &mut impl Analyzer,
+    ) -> bool {
+        let mut succeeded = true;
+        analyzer.before_all();
+        for (day, input) in numbered(inputs) {
+            match self.days.get(&day) {
+                Some(entry) => succeeded &= entry.run(None, input, options, analyzer),
+                None if options.quiet => {}
+                None => print_not_implemented(day),
+            }
+        }
+        analyzer.after_all();
+        succeeded
+    }
+
+    /// Like [run_some][Registry::run_some], but runs the days at the same time
+    ///
+    /// Each day reports to its own fork of `analyzer`. Its output is printed in order of days, once it
+    /// and the days before it finished.
+    pub fn run_some_parallel<A: ParallelAnalyzer>(
+        &self,
+        inputs: Vec<Option<Input<'_>>>,
+        options: &RunOptions,
+        analyzer: &mut A,
+    ) -> bool {
+        let turns = Turns::default();
+        let (implemented, missing): (Vec<_>, Vec<_>) =
+            numbered(inputs).enumerate().partition(|(_, (day, _))| self.days.contains_key(day));
+
+        analyzer.before_all();
+        let results = std::thread::scope(|scope| {
+            let mut handles = vec![];
+            for (idx, (day, input)) in implemented {
+                let entry = &self.days[&day];
+                let turn = turns.turn(idx);
+                let mut fork = analyzer.fork();
+                handles.push(scope.spawn(move || {
+                    let succeeded = entry.run_buffered(input, options, &mut fork, Box::new(|| turn.wait()));
+                    (succeeded, fork)
+                }));
+            }
+            for (idx, (day, _)) in missing {
+                let turn = turns.turn(idx);
+                turn.wait();
+                if !options.quiet {
+                    print_not_implemented(day);
+                }
+            }
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+        });
+
+        let mut succeeded = true;
+        for (day_succeeded, fork) in results {
+            analyzer.merge(fork);
+            succeeded &= day_succeeded;
+        }
+        analyzer.after_all();
+        succeeded
+    }
+
+    /// Runs the examples of one or all days and returns true if all of them passed
+    pub fn run_examples(&self, day: Option<usize>) -> bool {
+        match day {
+            Some(day) => match self.days.get(&day) {
+                Some(entry) => entry.run_examples(),
+                None => {
+                    print_not_implemented(day);
+                    false
+                }
+            },
+            None => {
+                let mut passed = true;
+                for entry in self.days.values() {
+                    passed &= entry.run_examples();
+                }
+                passed
+            }
+        }
+    }
+
+    /// Checks that all days where an input is given give the same answers however often and in
+    /// whichever order their phases run, see [check_idempotent][crate::check_idempotent]
+    ///
+    /// Returns true if none of them is inconsistent and all inputs could be read.
+    pub fn check_idempotent(&self, inputs: Vec<Option<Input<'_>>>) -> bool {
+        let mut consistent = true;
+        for (day, input) in numbered(inputs) {
+            let Some(entry) = self.days.get(&day) else {
+                print_not_implemented(day);
+                continue;
+            };
+            match input.into_string() {
+                Ok(input) => consistent &= entry.check_idempotent(&input),
+                Err(err) => {
+                    eprintln!("Day {}: could not read input, {}", day, err);
+                    consistent = false;
+                }
+            }
+        }
+        consistent
+    }
+}
+
+/// The registries of several years' events
+#[derive(Default)]
+pub struct Calendar {
+    years: BTreeMap<usize, Registry>,
+}
+
+impl Calendar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the days of `year`
+    ///
+    /// Panics if the year was already added.
+    pub fn add(&mut self, year: usize, registry: Registry) {
+        assert!(year >= FIRST_YEAR, "Invalid year: {}. Advent of Code started in {}", year, FIRST_YEAR);
+        let previous = self.years.insert(year, registry);
+        assert!(previous.is_none(), "Year {} is added twice", year);
+    }
+
+    /// Return all added years in ascending order
+    pub fn years(&self) -> impl Iterator<Item = usize> + '_ {
+        self.years.keys().copied()
+    }
+
+    /// Return the most recent year, if any
+    pub fn latest(&self) -> Option<usize> {
+        self.years.keys().next_back().copied()
+    }
+
+    /// Return the days of `year`, if it was added
+    pub fn get(&self, year: usize) -> Option<&Registry> {
+        self.years.get(&year)
+    }
+
+    /// Return the days of `year`, if it was added
+    pub fn get_mut(&mut self, year: usize) -> Option<&mut Registry> {
+        self.years.get_mut(&year)
+    }
+}
+
+/// Pairs each given input with its day number
+fn numbered<'a>(inputs: Vec<Option<Input<'a>>>) -> impl Iterator<Item = (usize, Input<'a>)> {
+    inputs
+        .into_iter()
+        .enumerate()
+        .filter_map(|(idx, input)| Some((idx + 1, input?)))
+}
+
+fn print_not_implemented(day: usize) {
+    println!("Day {}", day);
+    println!(" - not implemented");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{InMemory, InputProvider};
+
+    #[derive(Default)]
+    struct Answer42;
+
+    impl Day<'_> for Answer42 {
+        type Result1 = u8;
+        type Result2 = u8;
+
+        fn part1(&mut self) -> Self::Result1 {
+            42
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            42
+        }
+    }
+
+    impl Puzzle for Answer42 {
+        const DAY: usize = 3;
+        type Day<'input> = Self;
+    }
+
+    /// Panics on its example, which it gets wrong for part 2
+    #[derive(Default)]
+    struct Panicking;
+
+    impl Day<'_> for Panicking {
+        type Result1 = u8;
+        type Result2 = u8;
+
+        const EXAMPLES: &'static [crate::day::Example] = &[("", Some("1"), Some("2"))];
+
+        fn part1(&mut self) -> Self::Result1 {
+            panic!("example panicked")
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            42
+        }
+    }
+
+    impl Puzzle for Panicking {
+        const DAY: usize = 1;
+        type Day<'input> = Self;
+    }
+
+    struct Days(Vec<usize>);
+
+    impl Analyzer for Days {
+        fn before_day(&mut self, day: usize) {
+            self.0.push(day);
+        }
+    }
+
+    #[test]
+    fn skip_missing_days() {
+        let mut registry = Registry::new();
+        registry.register::<Answer42>();
+
+        let mut analyzer = Days(vec![]);
+        let inputs = InMemory::new().with(2025, 1, "").with(2025, 3, "").inputs(2025).unwrap();
+        assert!(registry.run_some(inputs, &RunOptions::default(), &mut analyzer));
+        assert_eq!(analyzer.0, vec![3]);
+        assert!(!registry.run_day(1, "", &RunOptions::default(), &mut analyzer));
+    }
+
+    #[test]
+    fn run_examples_past_panics() {
+        #[derive(Default)]
+        struct Counted;
+
+        static RUNS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+        impl Day<'_> for Counted {
+            type Result1 = usize;
+            type Result2 = usize;
+
+            const EXAMPLES: &'static [crate::day::Example] = &[("", Some("1"), None)];
+
+            fn part1(&mut self) -> Self::Result1 {
+                RUNS.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1
+            }
+
+            fn part2(&mut self) -> Self::Result2 {
+                0
+            }
+        }
+
+        impl Puzzle for Counted {
+            const DAY: usize = 2;
+            type Day<'input> = Self;
+        }
+
+        let mut registry = Registry::new();
+        registry.register::<Panicking>();
+        registry.register::<Counted>();
+
+        assert!(!registry.run_examples(Some(1)));
+        assert!(!registry.run_examples(None));
+        assert_eq!(RUNS.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,382 @@
+//! Machine-readable reports of measured run times
+
+use std::{fmt::Display, io::Write, path::PathBuf, str::FromStr, time::Duration};
+
+use crate::{InputDigest, Stats};
+
+/// Format of a [TimeAnalyzer][crate::TimeAnalyzer]'s report
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportFormat {
+    /// A table for the terminal, with human-readable times
+    #[default]
+    Table,
+    /// A JSON object with one entry per phase and the wall-clock time of all days, if measured
+    Json,
+    /// One line per phase
+    Csv,
+    /// A markdown table with one row per phase
+    Markdown,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Self::Table),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(format!("Invalid report format: '{}'. Valid formats are: table, json, csv, markdown", s)),
+        }
+    }
+}
+
+/// Where and how to write a report
+#[derive(Debug, Clone, Default)]
+pub struct Report {
+    pub format: ReportFormat,
+    /// File to write the report to, instead of stdout
+    pub path: Option<PathBuf>,
+}
+
+/// How a phase ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Failed,
+    Timeout,
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Failed => write!(f, "failed"),
+            Status::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ok" => Ok(Status::Ok),
+            "failed" => Ok(Status::Failed),
+            "timeout" => Ok(Status::Timeout),
+            _ => Err(format!("Invalid status: '{}'", s)),
+        }
+    }
+}
+
+/// The measurement of a single phase of a day, or of the whole day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub day: usize,
+    /// `parse`, `prepare`, `part1`, `part2` or `day` for the whole day
+    pub phase: &'static str,
+    /// Name of the [Variant][crate::Variant] that ran
+    pub variant: String,
+    pub status: Status,
+    /// Measured time, if the phase finished
+    pub time: Option<Duration>,
+    /// Rendered answer of a part
+    pub answer: Option<String>,
+    /// Statistics of the phase if it was measured more than once, `time` is their median then
+    pub stats: Option<Stats>,
+    /// Digest of the day's input, if it was read
+    pub input: Option<InputDigest>,
+}
+
+/// Writes `records` and the wall-clock time of all days in `format`
+///
+/// The wall-clock time is left out if it was not measured, e.g. when running a single day.
+///
+/// Statistics of repeated phases are only included if any phase was repeated.
+///
+/// Must not be called for [ReportFormat::Table], which is printed by the analyzer itself.
+pub fn write_report(
+    out: &mut dyn Write,
+    format: ReportFormat,
+    records: &[Record],
+    wall: Option<Duration>,
+) -> std::io::Result<()> {
+    match format {
+        ReportFormat::Table => unreachable!("tables are printed by the analyzer"),
+        ReportFormat::Json => write_json(out, records, wall),
+        ReportFormat::Csv => write_csv(out, records),
+        ReportFormat::Markdown => write_markdown(out, records),
+    }
+}
+
+fn write_json(out: &mut dyn Write, records: &[Record], wall: Option<Duration>) -> std::io::Result<()> {
+    let nanos = |time: Option<Duration>| time.map_or("null".to_string(), |time| time.as_nanos().to_string());
+
+    writeln!(out, "{{")?;
+    if let Some(wall) = wall {
+        writeln!(out, "  \"wall_ns\": {},", wall.as_nanos())?;
+    }
+    writeln!(out, "  \"results\": [")?;
+    for (idx, record) in records.iter().enumerate() {
+        let answer = record.answer.as_deref().map_or("null".to_string(), json_string);
+        write!(
+            out,
+            "    {{\"day\": {}, \"phase\": \"{}\", \"variant\": {}, \"status\": \"{}\", \"time_ns\": {}, \"answer\": {}",
+            record.day,
+            record.phase,
+            json_string(&record.variant),
+            record.status,
+            nanos(record.time),
+            answer,
+        )?;
+        if let Some(stats) = record.stats {
+            let [samples, min, median, mean, p95, stddev] = stats_fields(&stats);
+            write!(
+                out,
+                ", \"stats\": {{\"samples\": {}, \"min_ns\": {}, \"median_ns\": {}, \"mean_ns\": {}, \"p95_ns\": {}, \"stddev_ns\": {}}}",
+                samples, min, median, mean, p95, stddev,
+            )?;
+        }
+        write!(out, "}}")?;
+        writeln!(out, "{}", if idx + 1 < records.len() { "," } else { "" })?;
+    }
+    writeln!(out, "  ]")?;
+    writeln!(out, "}}")
+}
+
+pub(crate) fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+fn write_csv(out: &mut dyn Write, records: &[Record]) -> std::io::Result<()> {
+    let repeated = records.iter().any(|record| record.stats.is_some());
+    write!(out, "day,phase,variant,status,time_ns,answer")?;
+    if repeated {
+        write!(out, ",samples,min_ns,median_ns,mean_ns,p95_ns,stddev_ns")?;
+    }
+    writeln!(out)?;
+    for record in records {
+        write!(
+            out,
+            "{},{},{},{},{},{}",
+            record.day,
+            record.phase,
+            csv_field(&record.variant),
+            record.status,
+            record.time.map(|time| time.as_nanos().to_string()).unwrap_or_default(),
+            csv_field(record.answer.as_deref().unwrap_or_default()),
+        )?;
+        if repeated {
+            let fields = record.stats.as_ref().map(stats_fields).unwrap_or_default();
+            write!(out, ",{}", fields.join(","))?;
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+/// Number of samples, min, median, mean, p95 and standard deviation in nanoseconds
+fn stats_fields(stats: &Stats) -> [String; 6] {
+    [
+        stats.samples.to_string(),
+        stats.min.as_nanos().to_string(),
+        stats.median.as_nanos().to_string(),
+        stats.mean.as_nanos().to_string(),
+        stats.p95.as_nanos().to_string(),
+        stats.stddev.as_nanos().to_string(),
+    ]
+}
+
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn write_markdown(out: &mut dyn Write, records: &[Record]) -> std::io::Result<()> {
+    let repeated = records.iter().any(|record| record.stats.is_some());
+    write!(out, "| Day | Phase | Variant | Status | Time (ns) | Answer |")?;
+    if repeated {
+        write!(out, " Samples | Min (ns) | Median (ns) | Mean (ns) | p95 (ns) | Stddev (ns) |")?;
+    }
+    writeln!(out)?;
+    write!(out, "|----:|-------|---------|--------|----------:|--------|")?;
+    if repeated {
+        write!(out, "--------:|---------:|------------:|----------:|---------:|------------:|")?;
+    }
+    writeln!(out)?;
+    for record in records {
+        write!(
+            out,
+            "| {} | {} | {} | {} | {} | {} |",
+            record.day,
+            record.phase,
+            markdown_cell(&record.variant),
+            record.status,
+            record.time.map(|time| time.as_nanos().to_string()).unwrap_or_default(),
+            markdown_cell(record.answer.as_deref().unwrap_or_default()),
+        )?;
+        if repeated {
+            for field in record.stats.as_ref().map(stats_fields).unwrap_or_default() {
+                write!(out, " {} |", field)?;
+            }
+        }
+        writeln!(out)?;
+    }
+    Ok(())
+}
+
+fn markdown_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Width of the first column of a [Table], which labels the rows
+const LABEL_WIDTH: usize = 12;
+
+/// A table for the terminal, printed row by row, with a label per row and right-aligned cells
+pub(crate) struct Table {
+    /// Width of each column after the label
+    widths: Vec<usize>,
+}
+
+impl Table {
+    /// Prints the header of a table titled `title` with the given names and widths of its columns
+    pub(crate) fn start(title: &str, columns: &[(&str, usize)]) -> Self {
+        let table = Self {
+            widths: columns.iter().map(|(_, width)| *width).collect(),
+        };
+        let mut header = format!("| {:<1$} ", title, LABEL_WIDTH);
+        for (name, width) in columns {
+            header.push_str(&format!("| {:<1$} ", name, width));
+        }
+        header.push('|');
+
+        println!();
+        table.rule();
+        println!("{}", header);
+        table.rule();
+        table
+    }
+
+    /// Prints a row labeled `label`, with one cell per column
+    pub(crate) fn row(&self, label: &str, cells: impl IntoIterator<Item = String>) {
+        let mut line = format!("| {:>1$} |", label, LABEL_WIDTH);
+        for (cell, width) in cells.into_iter().zip(self.widths.iter()) {
+            line.push_str(&format!(" {:>1$} |", cell, width));
+        }
+        println!("{}", line);
+    }
+
+    /// Prints a line dividing the columns, which also ends the table
+    pub(crate) fn rule(&self) {
+        println!("{}", self.rule_line());
+    }
+
+    /// Prints a line spanning all columns, e.g. below the totals
+    pub(crate) fn separator(&self) {
+        println!("+{}+", "-".repeat(self.rule_line().chars().count() - 2));
+    }
+
+    fn rule_line(&self) -> String {
+        let mut rule = format!("+{}", "-".repeat(LABEL_WIDTH + 2));
+        for width in self.widths.iter() {
+            rule.push('|');
+            rule.push_str(&"-".repeat(width + 2));
+        }
+        rule.push('+');
+        rule
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 1,
+                phase: "part1",
+                variant: "default".to_string(),
+                status: Status::Ok,
+                time: Some(Duration::from_nanos(1234)),
+                answer: Some("#.\n.#".to_string()),
+                stats: None,
+                input: None,
+            },
+            Record {
+                day: 1,
+                phase: "part2",
+                variant: "default".to_string(),
+                status: Status::Failed,
+                time: None,
+                answer: None,
+                stats: None,
+                input: None,
+            },
+        ]
+    }
+
+    fn render(format: ReportFormat) -> String {
+        let mut out = vec![];
+        write_report(&mut out, format, &records(), Some(Duration::from_micros(5))).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn render_json() {
+        assert_eq!(
+            render(ReportFormat::Json),
+            concat!(
+                "{\n",
+                "  \"wall_ns\": 5000,\n",
+                "  \"results\": [\n",
+                "    {\"day\": 1, \"phase\": \"part1\", \"variant\": \"default\", \"status\": \"ok\", \"time_ns\": 1234, \"answer\": \"#.\\n.#\"},\n",
+                "    {\"day\": 1, \"phase\": \"part2\", \"variant\": \"default\", \"status\": \"failed\", \"time_ns\": null, \"answer\": null}\n",
+                "  ]\n",
+                "}\n",
+            )
+        );
+
+        let mut out = vec![];
+        write_report(&mut out, ReportFormat::Json, &records(), None).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("{\n  \"results\": [\n"));
+    }
+
+    #[test]
+    fn render_csv() {
+        assert_eq!(
+            render(ReportFormat::Csv),
+            "day,phase,variant,status,time_ns,answer\n1,part1,default,ok,1234,\"#.\n.#\"\n1,part2,default,failed,,\n"
+        );
+    }
+
+    #[test]
+    fn render_markdown() {
+        assert_eq!(
+            render(ReportFormat::Markdown),
+            concat!(
+                "| Day | Phase | Variant | Status | Time (ns) | Answer |\n",
+                "|----:|-------|---------|--------|----------:|--------|\n",
+                "| 1 | part1 | default | ok | 1234 | #.<br>.# |\n",
+                "| 1 | part2 | default | failed |  |  |\n",
+            )
+        );
+    }
+}
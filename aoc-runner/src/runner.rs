@@ -0,0 +1,1003 @@
+//! Runs a single day's puzzle, isolating panics of its parse and parts
+
+use std::{
+    io::BufRead,
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::mpsc::{self, RecvTimeoutError},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    analyzer::{Failure, Phase},
+    day::{Variant, DEFAULT_VARIANT},
+    input::DigestReader,
+    Analyzer, Answer, CancellationToken, Day, Input, InputDigest, ParseError, Puzzle, Trace, Tracer,
+};
+
+/// Options for running puzzles
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    /// Maximal run time of each part
+    ///
+    /// Parts exceeding it are cancelled (see [CancellationToken]) and reported as timed out.
+    pub timeout: Option<Duration>,
+    /// Name of the [Variant] to run instead of the default implementation
+    ///
+    /// Parts without a variant of that name run their default implementation.
+    pub variant: Option<String>,
+    /// Runs all variants of each part and fails the part if their answers differ
+    pub cross_check: bool,
+    /// Number of measured runs of each day, at least one
+    ///
+    /// Further runs parse the input into a fresh instance of the day and run the selected variant
+    /// of each part again, reporting only their run times to the analyzer.
+    pub repeat: usize,
+    /// Number of unmeasured runs of each day on fresh instances before the measured ones
+    pub warmup: usize,
+    /// Passes the [traces][Trace] each part sends via its [Tracer] to the analyzer while it runs
+    ///
+    /// Parts then run on a worker thread, like parts with a timeout do.
+    pub trace: bool,
+    /// Prints neither the days nor their answers, e.g. to keep stdout free for a machine-readable
+    /// report
+    ///
+    /// Errors are still printed to stderr.
+    pub quiet: bool,
+}
+
+/// Deferred printing of a day's progress and answers
+type Output<'a, D> = Box<dyn FnOnce(&D) + 'a>;
+
+/// Prints a day's output right away or buffers it until it is the day's turn to print
+struct Printer<'a, D> {
+    /// Waits for the day's turn, `None` if the output is printed right away
+    turn: Option<Box<dyn FnOnce() + 'a>>,
+    pending: Vec<Output<'a, D>>,
+    /// Drops the output meant for stdout, see [RunOptions::quiet]
+    quiet: bool,
+}
+
+impl<'a, D> Printer<'a, D> {
+    fn new(turn: Option<Box<dyn FnOnce() + 'a>>, quiet: bool) -> Self {
+        Self { turn, pending: vec![], quiet }
+    }
+
+    fn print(&mut self, day: &D, output: Output<'a, D>) {
+        if self.turn.is_some() {
+            self.pending.push(output);
+        } else {
+            output(day);
+        }
+    }
+
+    /// Prints output meant for stdout, unless the printer is quiet
+    fn stdout(&mut self, day: &D, output: Output<'a, D>) {
+        if !self.quiet {
+            self.print(day, output);
+        }
+    }
+
+    fn line(&mut self, day: &D, line: String) {
+        self.stdout(day, Box::new(move |_| println!("{}", line)));
+    }
+
+    /// Prints the buffered output once it is the day's turn
+    fn flush(self, day: &D) {
+        if let Some(turn) = self.turn {
+            turn();
+            for output in self.pending {
+                output(day);
+            }
+        }
+    }
+}
+
+/// Whether `D` parses a streamed input while it is read instead of getting it read into memory
+///
+/// Repeated days and days parsing their input again for each part get it read into memory, as do
+/// days not parsing streams.
+fn parses_stream<'input, D: Day<'input>>(options: &RunOptions) -> bool {
+    let repeated = options.repeat > 1 || options.warmup > 0;
+    D::STREAMING.is_some() && !repeated && !D::FRESH_PARTS
+}
+
+/// A message from the worker thread running a part
+enum Message {
+    Trace(Trace),
+    Finished(std::thread::Result<Answer>),
+}
+
+/// Result of running a single part
+enum Outcome {
+    Finished(Answer),
+    Panicked(String),
+    /// The part exceeded its timeout and returned once it was cancelled
+    TimedOut,
+}
+
+/// Runs one or both parts of the puzzle `P` on a fresh instance
+///
+/// Panics while parsing or running a part are caught and reported to `analyzer` via
+/// [on_failure][Analyzer::on_failure], so the remaining parts and days can still run. If the input
+/// cannot be read or parsed, no part is run.
+///
+/// Returns true if all phases finished successfully.
+pub fn run_day<'a, P: Puzzle>(
+    part: Option<usize>,
+    input: impl Into<Input<'a>>,
+    options: &RunOptions,
+    analyzer: &mut (impl Analyzer + ?Sized),
+) -> bool {
+    run_day_with::<P>(part, input.into(), options, analyzer, None)
+}
+
+/// Like [run_day], but buffers everything the day prints until it finished and `turn` returned
+///
+/// Used to run several days at the same time without interleaving their output, e.g. with a
+/// `turn` waiting until the days before it printed their output.
+pub fn run_day_buffered<'a, P: Puzzle>(
+    part: Option<usize>,
+    input: impl Into<Input<'a>>,
+    options: &RunOptions,
+    analyzer: &mut (impl Analyzer + ?Sized),
+    turn: impl FnOnce() + 'a,
+) -> bool {
+    run_day_with::<P>(part, input.into(), options, analyzer, Some(Box::new(turn)))
+}
+
+fn run_day_with<'a, P: Puzzle>(
+    part: Option<usize>,
+    input: Input<'a>,
+    options: &RunOptions,
+    analyzer: &mut (impl Analyzer + ?Sized),
+    turn: Option<Box<dyn FnOnce() + 'a>>,
+) -> bool {
+    // the runner owns the text it lends to the day
+    let mut stream = None;
+    let text = match input {
+        Input::Text(text) => Ok(Some(text.to_string())),
+        Input::Stream(input) if parses_stream::<P::Day<'static>>(options) => {
+            stream = Some(input);
+            Ok(None)
+        }
+        input => input.into_string().map(Some),
+    };
+
+    let mut day = P::Day::default();
+    let mut printer = Printer::new(turn, options.quiet);
+    let text = text.as_ref().map(Option::as_deref);
+    let succeeded = run_loaded(P::DAY, &mut day, part, text, stream, options, analyzer, &mut printer);
+    printer.flush(&day);
+    succeeded
+}
+
+/// Runs `day` on the text or stream of its input, which could not be read if `text` is an error
+#[allow(clippy::too_many_arguments)]
+fn run_loaded<'a, 'input, D>(
+    day_no: usize,
+    day: &mut D,
+    part: Option<usize>,
+    text: Result<Option<&'input str>, &std::io::Error>,
+    stream: Option<Box<dyn BufRead + Send + '_>>,
+    options: &RunOptions,
+    analyzer: &mut (impl Analyzer + ?Sized),
+    printer: &mut Printer<'a, D>,
+) -> bool
+where
+    D: Day<'input> + Send + 'a,
+{
+    let parts = match part {
+        Some(part @ (1 | 2)) => vec![part],
+        None => vec![1, 2],
+        Some(part) => panic!("Invalid part: {}. Valid parts are: 1,2", part),
+    };
+
+    printer.line(day, format!("Day {}", day_no));
+
+    analyzer.before_day(day_no);
+
+    let text = match text {
+        Ok(text) => text,
+        Err(err) => {
+            let message = format!("Day {}: could not read input, {}", day_no, err);
+            printer.print(day, Box::new(move |_| eprintln!("{}", message)));
+            analyzer.on_failure(day_no, Phase::Parse, &Failure::Read(err));
+            analyzer.after_day(day_no);
+            return false;
+        }
+    };
+
+    for _ in 0..options.warmup {
+        if !repeat_day::<D>(day_no, text.unwrap(), &parts, options, &mut ()) {
+            break;
+        }
+    }
+
+    analyzer.before_parse(day_no);
+    let (parsed, stream) = match (text, stream) {
+        (Some(text), _) => {
+            let parsed = catch_unwind(AssertUnwindSafe(|| day.parse(text).map(|()| prepare_with_parse(day))));
+            (parsed, None)
+        }
+        (None, Some(stream)) => {
+            let parse_stream = D::STREAMING.expect("only streaming days parse streams");
+            let mut stream = DigestReader::new(stream);
+            let parsed =
+                catch_unwind(AssertUnwindSafe(|| parse_stream(day, &mut stream).map(|()| prepare_with_parse(day))));
+            (parsed, Some(stream))
+        }
+        (None, None) => unreachable!("an input is either read into memory or streamed"),
+    };
+    analyzer.after_parse(day_no);
+    // a stream that was not parsed to its end is read to it, unless that fails
+    let digest = match stream {
+        Some(stream) => stream.finish().ok(),
+        None => Some(InputDigest::of(text.unwrap_or_default().as_bytes())),
+    };
+    if let Some(digest) = digest {
+        analyzer.on_input(day_no, &digest);
+    }
+
+    let mut succeeded = match parsed {
+        Ok(Ok(())) => true,
+        Ok(Err(err)) => {
+            let message = format!("Day {}: could not parse input, {:#}", day_no, err);
+            printer.print(day, Box::new(move |_| eprintln!("{}", message)));
+            analyzer.on_failure(day_no, Phase::Parse, &Failure::Parse(&err));
+            false
+        }
+        Err(payload) => {
+            analyzer.on_failure(day_no, Phase::Parse, &Failure::Panic(&panic_message(payload.as_ref())));
+            false
+        }
+    };
+
+    if succeeded && D::PREPARE {
+        if let Err(payload) = prepare(day_no, day, analyzer) {
+            analyzer.on_failure(day_no, Phase::Prepare, &Failure::Panic(&panic_message(payload.as_ref())));
+            succeeded = false;
+        }
+    }
+
+    if succeeded {
+        let mut fresh = FreshParts::new::<D>(text);
+        for &part in parts.iter() {
+            succeeded &= match part {
+                1 => {
+                    let variants = select_variants(D::part1, D::variants1(), options);
+                    run_part(day_no, 1, day, options, analyzer, printer, &mut fresh, variants, D::print_part1)
+                }
+                _ => {
+                    let variants = select_variants(D::part2, D::variants2(), options);
+                    run_part(day_no, 2, day, options, analyzer, printer, &mut fresh, variants, D::print_part2)
+                }
+            };
+        }
+    }
+
+    if succeeded {
+        for _ in 1..options.repeat {
+            if !repeat_day::<D>(day_no, text.unwrap(), &parts, options, analyzer) {
+                break;
+            }
+        }
+    }
+
+    analyzer.after_day(day_no);
+
+    succeeded
+}
+
+/// Parses `input` into a fresh instance of `D` and runs the selected variant of each of `parts`
+///
+/// Reports only the run times of the phases and timeouts to `analyzer`, neither answers nor
+/// failures. Returns false if a phase failed or timed out.
+fn repeat_day<'input, D>(
+    day_no: usize,
+    input: &'input str,
+    parts: &[usize],
+    options: &RunOptions,
+    analyzer: &mut (impl Analyzer + ?Sized),
+) -> bool
+where
+    D: Day<'input> + Send,
+{
+    let mut day = D::default();
+    analyzer.before_parse(day_no);
+    let parsed = catch_unwind(AssertUnwindSafe(|| day.parse(input).map(|()| prepare_with_parse(&mut day))));
+    analyzer.after_parse(day_no);
+    if !matches!(parsed, Ok(Ok(()))) {
+        return false;
+    }
+    if D::PREPARE && prepare(day_no, &mut day, analyzer).is_err() {
+        return false;
+    }
+
+    let mut fresh = FreshParts::new::<D>(Some(input));
+    for &part in parts {
+        let solve: Box<dyn FnOnce(&mut D) -> Answer + Send + '_> = match part {
+            1 => {
+                let (_, solve) = select_variants(D::part1, D::variants1(), options)[0];
+                Box::new(move |day| solve(day).into())
+            }
+            _ => {
+                let (_, solve) = select_variants(D::part2, D::variants2(), options)[0];
+                Box::new(move |day| solve(day).into())
+            }
+        };
+
+        if !matches!(fresh.prepare(&mut day), Ok(Ok(()))) {
+            return false;
+        }
+        analyzer.before_part(day_no, part);
+        let outcome = run_solver(&mut day, solve, options, &mut |_| {});
+        analyzer.after_part(day_no, part);
+        match outcome {
+            Outcome::Finished(_) => {}
+            Outcome::Panicked(_) => return false,
+            Outcome::TimedOut => {
+                analyzer.on_timeout(day_no, part, options.timeout.unwrap_or_default());
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Prepares a parsed day as part of parsing, unless it sets [PREPARE][Day::PREPARE]
+fn prepare_with_parse<'input, D: Day<'input>>(day: &mut D) {
+    if !D::PREPARE {
+        day.prepare();
+    }
+}
+
+/// Prepares a parsed day setting [PREPARE][Day::PREPARE] as its own phase
+fn prepare<'input, D: Day<'input>>(
+    day_no: usize,
+    day: &mut D,
+    analyzer: &mut (impl Analyzer + ?Sized),
+) -> std::thread::Result<()> {
+    analyzer.before_prepare(day_no);
+    let prepared = catch_unwind(AssertUnwindSafe(|| day.prepare()));
+    analyzer.after_prepare(day_no);
+    prepared
+}
+
+/// Parses and prepares the input into a fresh instance again before each further part of a day setting
+/// [FRESH_PARTS][Day::FRESH_PARTS]
+struct FreshParts<'input> {
+    /// The input to parse again, `None` if the day does not need it
+    input: Option<&'input str>,
+    /// Whether a part ran since the input was parsed
+    stale: bool,
+}
+
+impl<'input> FreshParts<'input> {
+    fn new<D: Day<'input>>(input: Option<&'input str>) -> Self {
+        Self { input: input.filter(|_| D::FRESH_PARTS), stale: false }
+    }
+
+    /// Prepares `day` for running a part
+    ///
+    /// Parsing and preparing again is not measured, neither as parsing nor as part of the part's
+    /// time. Fails if the input does not parse again, or if either panics.
+    fn prepare<D: Day<'input>>(&mut self, day: &mut D) -> std::thread::Result<Result<(), ParseError>> {
+        let mut prepared = Ok(Ok(()));
+        if let Some(input) = self.input.filter(|_| self.stale) {
+            *day = D::default();
+            prepared = catch_unwind(AssertUnwindSafe(|| day.parse(input).map(|()| day.prepare())));
+        }
+        self.stale = true;
+        prepared
+    }
+}
+
+/// The implementations of a part to run
+///
+/// Returns the [selected][RunOptions::variant] variant or the default implementation, followed by
+/// all other variants when [cross-checking][RunOptions::cross_check].
+fn select_variants<D, R>(
+    default: fn(&mut D) -> R,
+    variants: Vec<Variant<D, R>>,
+    options: &RunOptions,
+) -> Vec<Variant<D, R>> {
+    let mut all = vec![(DEFAULT_VARIANT, default)];
+    all.extend(variants);
+
+    let selected = options
+        .variant
+        .as_deref()
+        .and_then(|variant| all.iter().position(|(name, _)| *name == variant))
+        .unwrap_or(0);
+    all.swap(0, selected);
+    if !options.cross_check {
+        all.truncate(1);
+    }
+    all
+}
+
+/// Runs a single part of `day` using each of `variants` and prints their answers
+///
+/// The answer of the first variant is reported to `analyzer` and printed using `print`. Further
+/// variants must produce the same answer. Returns true if the part finished successfully.
+#[allow(clippy::too_many_arguments)]
+fn run_part<'a, 'input, D, R>(
+    day_no: usize,
+    part: usize,
+    day: &mut D,
+    options: &RunOptions,
+    analyzer: &mut (impl Analyzer + ?Sized),
+    printer: &mut Printer<'a, D>,
+    fresh: &mut FreshParts<'input>,
+    variants: Vec<Variant<D, R>>,
+    print: fn(&D, &Answer),
+) -> bool
+where
+    D: Day<'input> + Send + 'a,
+    R: Into<Answer> + 'static,
+{
+    let mut succeeded = true;
+    let mut answers = vec![];
+    for (idx, (name, solve)) in variants.into_iter().enumerate() {
+        let label = match name {
+            DEFAULT_VARIANT => format!("Part {}", part),
+            name => format!("Part {} ({})", part, name),
+        };
+        let solve = move |day: &mut D| -> Answer { solve(day).into() };
+
+        match fresh.prepare(day) {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => {
+                let message = format!("Day {}: could not parse input again, {:#}", day_no, err);
+                printer.print(day, Box::new(move |_| eprintln!("{}", message)));
+                printer.line(day, format!(" - {}: FAILED", label));
+                analyzer.on_failure(day_no, Phase::Part(part), &Failure::Parse(&err));
+                succeeded = false;
+                continue;
+            }
+            Err(payload) => {
+                printer.line(day, format!(" - {}: FAILED", label));
+                analyzer.on_failure(day_no, Phase::Part(part), &Failure::Panic(&panic_message(payload.as_ref())));
+                succeeded = false;
+                continue;
+            }
+        }
+        if idx > 0 {
+            analyzer.on_variant(day_no, part, name);
+        }
+        analyzer.before_part(day_no, part);
+        let outcome = run_solver(day, solve, options, &mut |trace| analyzer.on_trace(day_no, part, &trace));
+        analyzer.after_part(day_no, part);
+
+        match outcome {
+            Outcome::Finished(answer) => {
+                if idx == 0 {
+                    analyzer.on_answer(day_no, part, &answer);
+                }
+                if name == DEFAULT_VARIANT {
+                    let answer = answer.clone();
+                    printer.stdout(day, Box::new(move |day| print(day, &answer)));
+                } else {
+                    printer.line(day, format!(" - {}: {}", label, answer));
+                }
+                answers.push((name, answer));
+            }
+            Outcome::Panicked(message) => {
+                printer.line(day, format!(" - {}: FAILED", label));
+                analyzer.on_failure(day_no, Phase::Part(part), &Failure::Panic(&message));
+                succeeded = false;
+            }
+            Outcome::TimedOut => {
+                printer.line(day, format!(" - {}: TIMEOUT", label));
+                analyzer.on_timeout(day_no, part, options.timeout.unwrap_or_default());
+                succeeded = false;
+            }
+        }
+    }
+
+    if answers.iter().any(|(_, answer)| *answer != answers[0].1) {
+        printer.line(day, format!(" - Part {}: variants disagree", part));
+        analyzer.on_failure(day_no, Phase::Part(part), &Failure::Disagreement(&answers));
+        succeeded = false;
+    }
+
+    succeeded
+}
+
+/// Runs `solve` on `day`, passing the traces it sends to `on_trace`
+///
+/// Runs it on the current thread unless it has a timeout or is traced, see [solve_on_worker].
+fn run_solver<D, F>(day: &mut D, solve: F, options: &RunOptions, on_trace: &mut dyn FnMut(Trace)) -> Outcome
+where
+    D: Send,
+    F: FnOnce(&mut D) -> Answer + Send,
+{
+    if options.timeout.is_none() && !options.trace {
+        return match catch_unwind(AssertUnwindSafe(|| solve(day))) {
+            Ok(answer) => Outcome::Finished(answer),
+            Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+        };
+    }
+    solve_on_worker(day, solve, options.timeout, options.trace, on_trace)
+}
+
+/// Runs `solve` on a worker thread and cancels it after `timeout`, if there is one
+///
+/// A cancelled part is waited for until it returns, so parts that run long should poll their
+/// [CancellationToken]. If `trace` is set, the worker's [Tracer] passes the traces to `on_trace`
+/// while it runs.
+fn solve_on_worker<D, F>(
+    day: &mut D,
+    solve: F,
+    timeout: Option<Duration>,
+    trace: bool,
+    on_trace: &mut dyn FnMut(Trace),
+) -> Outcome
+where
+    D: Send,
+    F: FnOnce(&mut D) -> Answer + Send,
+{
+    let token = CancellationToken::new();
+    let (sender, receiver) = mpsc::channel();
+    let tracer = if trace {
+        let sender = sender.clone();
+        Tracer::new(move |trace| {
+            let _ = sender.send(Message::Trace(trace));
+        })
+    } else {
+        Tracer::default()
+    };
+    let worker_token = token.clone();
+
+    std::thread::scope(|scope| {
+        scope.spawn(move || {
+            worker_token.set_current();
+            tracer.set_current();
+            let result = catch_unwind(AssertUnwindSafe(|| solve(day)));
+            let _ = sender.send(Message::Finished(result));
+        });
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut cancelled = false;
+        let result = loop {
+            let received = match deadline.filter(|_| !cancelled) {
+                Some(deadline) => receiver.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(Message::Trace(trace)) => on_trace(trace),
+                Ok(Message::Finished(result)) => break result,
+                Err(RecvTimeoutError::Timeout) => {
+                    token.cancel();
+                    cancelled = true;
+                }
+                Err(RecvTimeoutError::Disconnected) => unreachable!("worker exited without sending a result"),
+            }
+        };
+
+        match result {
+            _ if cancelled => Outcome::TimedOut,
+            Ok(result) => Outcome::Finished(result),
+            Err(payload) => Outcome::Panicked(panic_message(payload.as_ref())),
+        }
+    })
+}
+
+/// Extracts the message of a caught panic
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_days::Draining;
+
+    /// A day whose part 2 only returns once it is cancelled
+    #[derive(Default)]
+    struct Endless;
+
+    impl Day<'_> for Endless {
+        type Result1 = u8;
+        type Result2 = u8;
+
+        fn part1(&mut self) -> Self::Result1 {
+            1
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            let token = CancellationToken::current();
+            while !token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            2
+        }
+    }
+
+    impl Puzzle for Endless {
+        const DAY: usize = 1;
+        type Day<'input> = Self;
+    }
+
+    /// A day whose part 1 has a variant with a different answer
+    #[derive(Default)]
+    struct Disagreeing;
+
+    impl Day<'_> for Disagreeing {
+        type Result1 = u8;
+        type Result2 = u8;
+
+        fn variants1() -> Vec<Variant<Self, Self::Result1>> {
+            vec![("off_by_one", |_| 2)]
+        }
+
+        fn part1(&mut self) -> Self::Result1 {
+            1
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            2
+        }
+    }
+
+    impl Puzzle for Disagreeing {
+        const DAY: usize = 1;
+        type Day<'input> = Self;
+    }
+
+    struct Timeouts(Vec<(usize, usize)>);
+
+    impl Analyzer for Timeouts {
+        fn on_timeout(&mut self, day: usize, part: usize, _timeout: Duration) {
+            self.0.push((day, part));
+        }
+    }
+
+    #[test]
+    fn cancel_part_after_timeout() {
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(10)),
+            ..Default::default()
+        };
+        let mut analyzer = Timeouts(vec![]);
+        assert!(!run_day::<Endless>(None, "", &options, &mut analyzer));
+        assert_eq!(analyzer.0, vec![(1, 2)]);
+    }
+
+    /// Counts the measured phases
+    #[derive(Default)]
+    struct Samples {
+        parse: usize,
+        prepare: usize,
+        parts: usize,
+    }
+
+    impl Analyzer for Samples {
+        fn after_parse(&mut self, _day: usize) {
+            self.parse += 1;
+        }
+
+        fn after_prepare(&mut self, _day: usize) {
+            self.prepare += 1;
+        }
+
+        fn after_part(&mut self, _day: usize, _part: usize) {
+            self.parts += 1;
+        }
+    }
+
+    #[test]
+    fn repeat_measurements() {
+        let options = RunOptions {
+            repeat: 3,
+            warmup: 2,
+            ..Default::default()
+        };
+        let mut analyzer = Samples::default();
+        assert!(run_day::<Endless>(Some(1), "", &options, &mut analyzer));
+        assert_eq!((analyzer.parse, analyzer.parts), (3, 3));
+    }
+
+    /// A day whose part 1 only returns once it is cancelled after its first run
+    #[derive(Default)]
+    struct Tiring;
+
+    static TIRING_RUNS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    impl Day<'_> for Tiring {
+        type Result1 = u8;
+        type Result2 = u8;
+
+        fn part1(&mut self) -> Self::Result1 {
+            if TIRING_RUNS.fetch_add(1, std::sync::atomic::Ordering::Relaxed) > 0 {
+                let token = CancellationToken::current();
+                while !token.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            }
+            1
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            2
+        }
+    }
+
+    impl Puzzle for Tiring {
+        const DAY: usize = 1;
+        type Day<'input> = Self;
+    }
+
+    #[test]
+    fn report_timed_out_repeats() {
+        let options = RunOptions {
+            timeout: Some(Duration::from_millis(10)),
+            repeat: 3,
+            ..Default::default()
+        };
+        let mut analyzer = (Timeouts(vec![]), Samples::default());
+        assert!(run_day::<Tiring>(Some(1), "", &options, &mut analyzer));
+        assert_eq!(analyzer.0 .0, vec![(1, 1)]);
+        assert_eq!(analyzer.1.parts, 2);
+    }
+
+    #[test]
+    fn cross_check_variants() {
+        let mut analyzer = Timeouts(vec![]);
+        let options = RunOptions {
+            variant: Some("off_by_one".to_string()),
+            ..Default::default()
+        };
+        assert!(run_day::<Disagreeing>(None, "", &options, &mut analyzer));
+
+        let options = RunOptions {
+            cross_check: true,
+            ..Default::default()
+        };
+        assert!(!run_day::<Disagreeing>(None, "", &options, &mut analyzer));
+    }
+
+    /// Records the inputs and failures
+    #[derive(Default)]
+    struct Events(Vec<String>);
+
+    impl Analyzer for Events {
+        fn on_input(&mut self, day: usize, input: &InputDigest) {
+            self.0.push(format!("day {}: {}", day, input));
+        }
+
+        fn on_failure(&mut self, day: usize, phase: Phase, failure: &Failure) {
+            self.0.push(format!("day {}: {} {}", day, phase, failure));
+        }
+    }
+
+    #[test]
+    fn report_inputs_and_failures() {
+        let options = RunOptions {
+            cross_check: true,
+            ..Default::default()
+        };
+        let mut analyzer = Events::default();
+        run_day::<Disagreeing>(Some(1), "abc", &options, &mut analyzer);
+        run_day::<Disagreeing>(Some(1), Input::stream("abc".as_bytes()), &options, &mut analyzer);
+        assert_eq!(
+            analyzer.0,
+            vec![
+                "day 1: 3 bytes, hash e71fa2190541574b",
+                "day 1: part 1 variants disagree, default = 1, off_by_one = 2",
+                "day 1: 3 bytes, hash e71fa2190541574b",
+                "day 1: part 1 variants disagree, default = 1, off_by_one = 2",
+            ]
+        );
+    }
+
+    /// A day setting FRESH_PARTS whose input only parses once
+    #[derive(Default)]
+    struct Fickle;
+
+    static FICKLE_PARSES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    impl Day<'_> for Fickle {
+        type Result1 = u8;
+        type Result2 = u8;
+
+        const FRESH_PARTS: bool = true;
+
+        fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+            match FICKLE_PARSES.fetch_add(1, std::sync::atomic::Ordering::Relaxed) {
+                0 => Ok(()),
+                _ => Err(ParseError::eof(input, "the same input")),
+            }
+        }
+
+        fn part1(&mut self) -> Self::Result1 {
+            1
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            2
+        }
+    }
+
+    impl Puzzle for Fickle {
+        const DAY: usize = 1;
+        type Day<'input> = Self;
+    }
+
+    #[test]
+    fn report_failures_parsing_again() {
+        let mut analyzer = Events::default();
+        assert!(!run_day::<Fickle>(None, "", &RunOptions::default(), &mut analyzer));
+        assert_eq!(
+            analyzer.0,
+            vec!["day 1: 0 bytes, hash cbf29ce484222325", "day 1: part 2 line 1, column 1: expected the same input, found nothing"]
+        );
+    }
+
+    /// A day whose part 1 reports its progress
+    #[derive(Default)]
+    struct Traced;
+
+    impl Day<'_> for Traced {
+        type Result1 = u8;
+        type Result2 = u8;
+
+        fn part1(&mut self) -> Self::Result1 {
+            let tracer = Tracer::current();
+            tracer.progress(1, 2);
+            tracer.trace("halfway");
+            tracer.progress(2, 2);
+            1
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            2
+        }
+    }
+
+    impl Puzzle for Traced {
+        const DAY: usize = 1;
+        type Day<'input> = Self;
+    }
+
+    struct Traces(Vec<(usize, Trace)>);
+
+    impl Analyzer for Traces {
+        fn on_trace(&mut self, _day: usize, part: usize, trace: &Trace) {
+            self.0.push((part, trace.clone()));
+        }
+    }
+
+    #[test]
+    fn pass_traces_to_analyzer() {
+        let mut analyzer = Traces(vec![]);
+        assert!(run_day::<Traced>(None, "", &RunOptions::default(), &mut analyzer));
+        assert_eq!(analyzer.0, vec![]);
+
+        let options = RunOptions {
+            trace: true,
+            ..Default::default()
+        };
+        assert!(run_day::<Traced>(None, "", &options, &mut analyzer));
+        assert_eq!(
+            analyzer.0,
+            vec![
+                (1, Trace::Progress { done: 1, total: 2 }),
+                (1, Trace::Message("halfway".to_string())),
+                (1, Trace::Progress { done: 2, total: 2 }),
+            ]
+        );
+    }
+
+    #[test]
+    fn run_parts_on_fresh_instances() {
+        let options = RunOptions {
+            cross_check: true,
+            ..Default::default()
+        };
+        assert!(run_day::<Draining<true>>(None, "abc", &options, &mut ()));
+        assert!(run_day::<Draining<true>>(None, Input::stream("abc".as_bytes()), &options, &mut ()));
+    }
+
+    /// A day sharing the sum of its input's digits between both parts
+    #[derive(Default)]
+    struct Summing {
+        digits: Vec<u32>,
+        sum: Option<u32>,
+    }
+
+    impl<'input> Day<'input> for Summing {
+        type Result1 = u32;
+        type Result2 = u32;
+
+        const PREPARE: bool = true;
+
+        fn parse(&mut self, input: &'input str) -> Result<(), crate::ParseError> {
+            self.digits = input.chars().filter_map(|c| c.to_digit(10)).collect();
+            Ok(())
+        }
+
+        fn prepare(&mut self) {
+            self.sum = Some(self.digits.iter().sum());
+        }
+
+        fn part1(&mut self) -> Self::Result1 {
+            self.sum.unwrap()
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            self.sum.unwrap() * 2
+        }
+    }
+
+    impl Puzzle for Summing {
+        const DAY: usize = 1;
+        type Day<'input> = Self;
+    }
+
+    #[test]
+    fn measure_prepare_phase() {
+        let options = RunOptions {
+            repeat: 2,
+            ..Default::default()
+        };
+        let mut analyzer = Samples::default();
+        assert!(run_day::<Summing>(None, "123", &options, &mut analyzer));
+        assert_eq!((analyzer.parse, analyzer.prepare, analyzer.parts), (2, 2, 4));
+    }
+
+    /// A day borrowing the lines of its input
+    #[derive(Default)]
+    struct Borrowing<'input>(Vec<&'input str>);
+
+    impl<'input> Day<'input> for Borrowing<'input> {
+        type Result1 = String;
+        type Result2 = usize;
+
+        fn parse(&mut self, input: &'input str) -> Result<(), crate::ParseError> {
+            self.0 = input.lines().collect();
+            Ok(())
+        }
+
+        fn part1(&mut self) -> Self::Result1 {
+            self.0.concat()
+        }
+
+        fn part2(&mut self) -> Self::Result2 {
+            self.0.len()
+        }
+    }
+
+    impl Puzzle for Borrowing<'static> {
+        const DAY: usize = 1;
+        type Day<'input> = Borrowing<'input>;
+    }
+
+    struct Answers(Vec<Answer>);
+
+    impl Analyzer for Answers {
+        fn on_answer(&mut self, _day: usize, _part: usize, answer: &Answer) {
+            self.0.push(answer.clone());
+        }
+    }
+
+    #[test]
+    fn lend_input_to_day() {
+        let options = RunOptions {
+            timeout: Some(Duration::from_secs(10)),
+            repeat: 2,
+            ..Default::default()
+        };
+        let mut analyzer = Answers(vec![]);
+        let input = Input::stream("ab\ncd\n".as_bytes());
+        assert!(run_day::<Borrowing>(None, input, &options, &mut analyzer));
+        assert_eq!(analyzer.0, vec![Answer::from("abcd".to_string()), Answer::from(2usize)]);
+    }
+}
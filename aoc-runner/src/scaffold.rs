@@ -0,0 +1,214 @@
+//! Generates the skeleton of a new day and registers it in the crate's modules and benchmarks
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Where a crate keeps its days and benchmarks
+#[derive(Debug, Clone)]
+pub struct Scaffold {
+    /// Name of the crate as used in paths, e.g. `aoc2025`
+    pub krate: String,
+    /// Directory of the day modules, holding a `mod.rs` declaring them
+    pub days_dir: PathBuf,
+    /// Benchmarks declaring each day with `bench_day!`, if there are any
+    pub bench: Option<PathBuf>,
+}
+
+impl Scaffold {
+    /// Creates `dayNN.rs` from a template and adds the day to `mod.rs` and the benchmarks
+    ///
+    /// The module starts with `title`, if given, e.g. one read with [puzzle_title]. Fails without
+    /// changing anything if the day's module exists already, or if `mod.rs` or the benchmarks have
+    /// no declarations of other days to add the day to. Returns the changed files.
+    pub fn create_day(&self, day: usize, title: Option<&str>) -> Result<Vec<PathBuf>, String> {
+        let path = self.days_dir.join(format!("day{:0>2}.rs", day));
+        if path.exists() {
+            return Err(format!("{} exists already", path.display()));
+        }
+
+        let mod_path = self.days_dir.join("mod.rs");
+        let modules = read(&mod_path)?;
+        let mut edits = vec![
+            (path, String::new(), template(day, title)),
+            (mod_path.clone(), modules.clone(), declare_module(&modules, day).map_err(|err| at(&mod_path, err))?),
+        ];
+        if let Some(bench) = &self.bench {
+            let benches = read(bench)?;
+            let declared = declare_bench(&benches, &self.krate, day).map_err(|err| at(bench, err))?;
+            edits.push((bench.clone(), benches, declared));
+        }
+
+        let mut changed = vec![];
+        for (path, _, after) in edits.into_iter().filter(|(_, before, after)| before != after) {
+            write(&path, &after)?;
+            changed.push(path);
+        }
+        Ok(changed)
+    }
+}
+
+/// The title of a puzzle, e.g. `Christmas Tree Farm`, from its description's
+/// `--- Day 12: Christmas Tree Farm ---` heading
+pub fn puzzle_title(page: &str) -> Option<String> {
+    let (_, heading) = page.split_once("--- Day ")?;
+    let (heading, _) = heading.split_once(" ---")?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim().to_string())
+}
+
+/// The module of a new day
+fn template(day: usize, title: Option<&str>) -> String {
+    let heading = match title {
+        Some(title) => format!("Day {:0>2} {}", day, title),
+        None => format!("Day {:0>2}", day),
+    };
+    format!(
+        r#"//! # {heading}
+
+use aoc_runner::{{Answer, Day, Example, ParseError}};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
+
+#[aoc(day = {day})]
+#[derive(Default, Clone)]
+pub struct Day{day:0>2} {{
+}}
+
+impl Day<'_> for Day{day:0>2} {{
+    type Result1 = Answer;
+    type Result2 = Answer;
+
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, None, None)];
+
+    fn parse(&mut self, _input: &str) -> Result<(), ParseError> {{
+        Ok(())
+    }}
+
+    fn part1(&mut self) -> Self::Result1 {{
+        Answer::Unsolved
+    }}
+
+    fn part2(&mut self) -> Self::Result2 {{
+        Answer::Unsolved
+    }}
+}}
+
+const EXAMPLE: &str = indoc! {{"
+"}};
+
+#[cfg(test)]
+mod test {{
+    use super::*;
+
+    aoc_runner::example_tests!(Day{day:0>2});
+}}
+"#
+    )
+}
+
+/// Adds `pub mod dayNN;` to the module declarations after the ones of earlier days, unless it is
+/// declared already
+fn declare_module(modules: &str, day: usize) -> Result<String, String> {
+    let declaration = format!("pub mod day{:0>2};", day);
+    insert_sorted(modules, &declaration, |line| line.starts_with("pub mod day"))
+        .ok_or_else(|| "no `pub mod dayNN;` declarations to add the day to".to_string())
+}
+
+/// Adds the day's `bench_day!` invocation and its group in `criterion_main!` to the benchmarks,
+/// unless it has them already
+fn declare_bench(benches: &str, krate: &str, day: usize) -> Result<String, String> {
+    let invocation = format!("bench_day!({}, day_{:0>2}, {}::days::day{:0>2}::Day{:0>2});", day, day, krate, day, day);
+    let group = format!("    day_{:0>2}::bench,", day);
+    let benches = insert_sorted(benches, &invocation, |line| line.starts_with("bench_day!("))
+        .ok_or_else(|| "no `bench_day!` invocations to add the day to".to_string())?;
+    let is_group = |line: &str| line.trim_start().starts_with("day_") && line.ends_with("::bench,");
+    insert_sorted(&benches, &group, is_group)
+        .ok_or_else(|| "no `day_NN::bench,` groups in `criterion_main!` to add the day to".to_string())
+}
+
+/// Inserts `line` into the block of lines matching `block`, keeping it sorted, returns `text`
+/// unchanged if it has the line already and `None` if there is no such block
+fn insert_sorted(text: &str, line: &str, block: impl Fn(&str) -> bool) -> Option<String> {
+    let mut lines = text.lines().collect::<Vec<_>>();
+    let last = lines.iter().rposition(|it| block(it))?;
+    if lines.contains(&line) {
+        return Some(text.to_string());
+    }
+    // sorting by length first orders day_9 before day_10 for unpadded numbers
+    let key = |it: &str| (it.len(), it.to_string());
+    let idx = lines
+        .iter()
+        .position(|it| block(it) && key(it) > key(line))
+        .unwrap_or(last + 1);
+
+    lines.insert(idx, line);
+    let mut result = lines.join("\n");
+    if text.ends_with('\n') {
+        result.push('\n');
+    }
+    Some(result)
+}
+
+/// Prefixes an error with the file it concerns
+fn at(path: &Path, err: String) -> String {
+    format!("{}: {}", path.display(), err)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|err| at(path, err.to_string()))
+}
+
+fn write(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|err| at(path, err.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn create_day() {
+        let root = std::env::temp_dir().join(format!("aoc-runner-scaffold-{}", std::process::id()));
+        let days_dir = root.join("days");
+        fs::create_dir_all(&days_dir).unwrap();
+        fs::write(days_dir.join("mod.rs"), "pub mod day01;\npub mod day03;\n\npub const YEAR: usize = 2025;\n").unwrap();
+        let bench = root.join("bench.rs");
+        fs::write(
+            &bench,
+            "bench_day!(1, day_01, aoc::days::day01::Day01);\n\ncriterion_main!(\n    day_01::bench,\n);\n",
+        )
+        .unwrap();
+        let scaffold = Scaffold { krate: "aoc".to_string(), days_dir: days_dir.clone(), bench: Some(bench.clone()) };
+
+        let changed = scaffold.create_day(2, Some("Gift Shop")).unwrap();
+        assert_eq!(changed, vec![days_dir.join("day02.rs"), days_dir.join("mod.rs"), bench.clone()]);
+        assert!(fs::read_to_string(days_dir.join("day02.rs")).unwrap().starts_with("//! # Day 02 Gift Shop\n"));
+        assert_eq!(
+            fs::read_to_string(days_dir.join("mod.rs")).unwrap(),
+            "pub mod day01;\npub mod day02;\npub mod day03;\n\npub const YEAR: usize = 2025;\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&bench).unwrap(),
+            "bench_day!(1, day_01, aoc::days::day01::Day01);\nbench_day!(2, day_02, aoc::days::day02::Day02);\n\n\
+             criterion_main!(\n    day_01::bench,\n    day_02::bench,\n);\n"
+        );
+        assert!(scaffold.create_day(2, None).is_err());
+
+        // nothing is written if a file has nowhere to declare the day
+        fs::write(&bench, "criterion_main!(\n    day_01::bench,\n);\n").unwrap();
+        let err = scaffold.create_day(4, None).unwrap_err();
+        assert!(err.contains("bench_day!"), "{}", err);
+        assert!(!days_dir.join("day04.rs").exists());
+        assert!(!fs::read_to_string(days_dir.join("mod.rs")).unwrap().contains("day04"));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn read_puzzle_title() {
+        let page = "<main><article class=\"day-desc\"><h2>--- Day 12: Christmas Tree Farm ---</h2><p>...</p>";
+        assert_eq!(puzzle_title(page), Some("Christmas Tree Farm".to_string()));
+        assert_eq!(puzzle_title("<main></main>"), None);
+    }
+}
@@ -0,0 +1,69 @@
+//! Statistics of repeatedly measured run times
+
+use std::time::Duration;
+
+/// Statistics of the run times of a phase measured several times
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of measurements
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    /// 95th percentile, using the nearest rank
+    pub p95: Duration,
+    /// Sample standard deviation
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Computes the statistics of `samples`, if there are any
+    pub fn of(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let len = sorted.len();
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+        let mean = sorted.iter().sum::<Duration>() / len as u32;
+        let variance = if len > 1 {
+            sorted.iter().map(|sample| (sample.as_secs_f64() - mean.as_secs_f64()).powi(2)).sum::<f64>() / (len - 1) as f64
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            samples: len,
+            min: sorted[0],
+            median,
+            mean,
+            p95: sorted[(len * 95).div_ceil(100) - 1],
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stats_of_samples() {
+        let samples = [5, 1, 4, 2, 3, 100].map(Duration::from_micros);
+        let stats = Stats::of(&samples).unwrap();
+        assert_eq!(stats.samples, 6);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_nanos(3500));
+        assert_eq!(stats.mean.as_micros(), 19);
+        assert_eq!(stats.p95, Duration::from_micros(100));
+        assert_eq!(stats.stddev.as_micros(), 39);
+
+        assert_eq!(Stats::of(&[]), None);
+    }
+}
@@ -0,0 +1,38 @@
+//! Days shared by the tests of several modules
+
+use crate::{Day, ParseError, Puzzle, Variant};
+
+/// A day whose part 2 consumes the parsed state part 1 depends on, with a variant doing the same
+///
+/// `FRESH_PARTS` tells the runner to run each part on a fresh instance, see [Day::FRESH_PARTS].
+#[derive(Default)]
+pub(crate) struct Draining<const FRESH_PARTS: bool>(Vec<u8>);
+
+impl<'input, const FRESH_PARTS: bool> Day<'input> for Draining<FRESH_PARTS> {
+    type Result1 = usize;
+    type Result2 = usize;
+
+    const FRESH_PARTS: bool = FRESH_PARTS;
+
+    fn variants2() -> Vec<Variant<Self, Self::Result2>> {
+        vec![("again", Self::part2)]
+    }
+
+    fn parse(&mut self, input: &'input str) -> Result<(), ParseError> {
+        self.0.extend(input.bytes());
+        Ok(())
+    }
+
+    fn part1(&mut self) -> Self::Result1 {
+        self.0.len()
+    }
+
+    fn part2(&mut self) -> Self::Result2 {
+        self.0.drain(..).count()
+    }
+}
+
+impl<const FRESH_PARTS: bool> Puzzle for Draining<FRESH_PARTS> {
+    const DAY: usize = 1;
+    type Day<'input> = Self;
+}
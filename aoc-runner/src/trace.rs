@@ -0,0 +1,325 @@
+//! Progress and trace messages sent by long running parts while they run
+
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{report::json_string, Analyzer, ParallelAnalyzer};
+
+thread_local! {
+    static CURRENT: RefCell<Option<Tracer>> = const { RefCell::new(None) };
+}
+
+/// An event sent by a part while it runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trace {
+    /// `done` of `total` steps of the part are done
+    Progress { done: usize, total: usize },
+    /// A free-form message, e.g. an intermediate result
+    Message(String),
+}
+
+/// Sends [traces][Trace] of the running part to the analyzer
+///
+/// Traces are only delivered if the runner [traces][crate::RunOptions::trace], otherwise sending
+/// them does nothing.
+///
+/// # Example
+/// ```ignore
+/// fn part2(&mut self) -> Self::Result2 {
+///     let counter = Tracer::current().counter(self.items.len());
+///     self.items.par_iter().map(|item| {
+///         let result = solve(item);
+///         counter.tick();
+///         result
+///     }).sum()
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Tracer(Option<Arc<dyn Fn(Trace) + Send + Sync>>);
+
+impl Tracer {
+    /// Creates a tracer passing each trace to `send`
+    pub fn new(send: impl Fn(Trace) + Send + Sync + 'static) -> Self {
+        Self(Some(Arc::new(send)))
+    }
+
+    /// The tracer of the part running on the current thread
+    ///
+    /// Returns a tracer that drops all traces if the part runs without tracing. Like a
+    /// [CancellationToken][crate::CancellationToken], it is not inherited by worker threads spawned
+    /// by the part, so it must be obtained before and passed to them.
+    pub fn current() -> Self {
+        CURRENT.with(|current| current.borrow().clone().unwrap_or_default())
+    }
+
+    /// Makes this tracer the [current][Tracer::current] one of this thread
+    pub(crate) fn set_current(&self) {
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+    }
+
+    /// Returns true if traces are delivered to an analyzer
+    pub fn is_active(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Reports that `done` of `total` steps are done
+    pub fn progress(&self, done: usize, total: usize) {
+        self.send(Trace::Progress { done, total });
+    }
+
+    /// Sends a message, e.g. an intermediate result
+    pub fn trace(&self, message: impl Display) {
+        if self.is_active() {
+            self.send(Trace::Message(message.to_string()));
+        }
+    }
+
+    /// Creates a counter of `total` steps reporting its progress to this tracer
+    pub fn counter(&self, total: usize) -> Counter {
+        Counter {
+            tracer: self.clone(),
+            done: Arc::new(AtomicUsize::new(0)),
+            total,
+        }
+    }
+
+    fn send(&self, trace: Trace) {
+        if let Some(send) = &self.0 {
+            send(trace);
+        }
+    }
+}
+
+/// Counts the steps of a part that are done, e.g. by several threads, see [Tracer::counter]
+///
+/// Reports its progress whenever another percent of the steps is done, so it can be ticked often.
+#[derive(Clone)]
+pub struct Counter {
+    tracer: Tracer,
+    done: Arc<AtomicUsize>,
+    total: usize,
+}
+
+impl Counter {
+    /// Counts another step as done
+    pub fn tick(&self) {
+        if !self.tracer.is_active() {
+            return;
+        }
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if (done * 100 / self.total.max(1)) != ((done - 1) * 100 / self.total.max(1)) || done == self.total {
+            self.tracer.progress(done, self.total);
+        }
+    }
+}
+
+/// Minimal time between two redraws of the progress bar
+const REDRAW_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Width of the progress bar in chars
+const BAR_WIDTH: usize = 30;
+
+/// An analyzer drawing a progress bar on stderr for parts reporting their [progress][Tracer::progress]
+///
+/// Days running at the same time draw no progress bars, as they would overwrite each other's.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProgressAnalyzer {
+    last_redraw: Option<Instant>,
+    drawn: bool,
+    forked: bool,
+}
+
+impl ProgressAnalyzer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn clear(&mut self) {
+        if self.drawn {
+            eprint!("\r\x1b[2K");
+            self.drawn = false;
+        }
+    }
+}
+
+impl Analyzer for ProgressAnalyzer {
+    fn after_part(&mut self, _day: usize, _part: usize) {
+        self.clear();
+        self.last_redraw = None;
+    }
+
+    fn on_trace(&mut self, day: usize, part: usize, trace: &Trace) {
+        let Trace::Progress { done, total } = *trace else {
+            return;
+        };
+        if self.forked {
+            return;
+        }
+        if self.last_redraw.is_some_and(|last| last.elapsed() < REDRAW_INTERVAL) && done < total {
+            return;
+        }
+        self.last_redraw = Some(Instant::now());
+
+        let filled = (done * BAR_WIDTH / total.max(1)).min(BAR_WIDTH);
+        eprint!(
+            "\r\x1b[2KDay {} part {} [{}{}] {}/{}",
+            day,
+            part,
+            "#".repeat(filled),
+            " ".repeat(BAR_WIDTH - filled),
+            done,
+            total
+        );
+        self.drawn = true;
+    }
+}
+
+impl ParallelAnalyzer for ProgressAnalyzer {
+    fn fork(&self) -> Self {
+        Self { forked: true, ..Self::default() }
+    }
+
+    fn merge(&mut self, _other: Self) {}
+}
+
+/// An analyzer writing all [traces][Trace] to a file, one JSON object per line
+///
+/// Each line holds the time since the start of the run, the day and part, and either the progress
+/// (`done` and `total`) or the `message`.
+pub struct TraceAnalyzer {
+    start: Instant,
+    /// The file to write to, or `None` for a fork or once writing failed
+    out: Option<BufWriter<File>>,
+    forked: bool,
+    /// Traces of a fork, and the traces of all merged forks, which are written sorted by day once
+    /// all days ran
+    pending: Vec<(Duration, usize, usize, Trace)>,
+}
+
+impl TraceAnalyzer {
+    /// Creates an analyzer writing to the file at `path`, replacing it if it exists
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self {
+            start: Instant::now(),
+            out: Some(BufWriter::new(File::create(path)?)),
+            forked: false,
+            pending: vec![],
+        })
+    }
+
+    fn write(&mut self, elapsed: Duration, day: usize, part: usize, trace: &Trace) {
+        if self.forked {
+            self.pending.push((elapsed, day, part, trace.clone()));
+            return;
+        }
+        let Some(out) = &mut self.out else {
+            return;
+        };
+        let event = match trace {
+            Trace::Progress { done, total } => format!("\"done\": {}, \"total\": {}", done, total),
+            Trace::Message(message) => format!("\"message\": {}", json_string(message)),
+        };
+        let written = writeln!(
+            out,
+            "{{\"elapsed_ns\": {}, \"day\": {}, \"part\": {}, {}}}",
+            elapsed.as_nanos(),
+            day,
+            part,
+            event
+        );
+        if let Err(err) = written {
+            eprintln!("Could not write trace: {}", err);
+            self.out = None;
+        }
+    }
+}
+
+impl Analyzer for TraceAnalyzer {
+    fn before_all(&mut self) {
+        self.start = Instant::now();
+    }
+
+    fn after_all(&mut self) {
+        // forks finish in any order, the traces of each day are already in order
+        let mut pending = std::mem::take(&mut self.pending);
+        pending.sort_by_key(|(_, day, _, _)| *day);
+        for (elapsed, day, part, trace) in pending {
+            self.write(elapsed, day, part, &trace);
+        }
+        if let Some(Err(err)) = self.out.as_mut().map(|out| out.flush()) {
+            eprintln!("Could not write trace: {}", err);
+        }
+    }
+
+    fn on_trace(&mut self, day: usize, part: usize, trace: &Trace) {
+        self.write(self.start.elapsed(), day, part, trace);
+    }
+}
+
+impl ParallelAnalyzer for TraceAnalyzer {
+    fn fork(&self) -> Self {
+        Self { start: self.start, out: None, forked: true, pending: vec![] }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.pending.extend(other.pending);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn count_progress_in_percent() {
+        let traces = Arc::new(Mutex::new(vec![]));
+        let tracer = {
+            let traces = traces.clone();
+            Tracer::new(move |trace| traces.lock().unwrap().push(trace))
+        };
+        let counter = tracer.counter(1000);
+        for _ in 0..1000 {
+            counter.tick();
+        }
+        let traces = traces.lock().unwrap();
+        assert_eq!(traces.len(), 100);
+        assert_eq!(traces[0], Trace::Progress { done: 10, total: 1000 });
+        assert_eq!(traces[99], Trace::Progress { done: 1000, total: 1000 });
+
+        // an inactive tracer drops everything
+        Tracer::current().counter(10).tick();
+    }
+
+    #[test]
+    fn write_merged_traces_by_day() {
+        let path = std::env::temp_dir().join(format!("aoc-runner-trace-{}.jsonl", std::process::id()));
+        let mut analyzer = TraceAnalyzer::create(&path).unwrap();
+        analyzer.before_all();
+        let forks = [2, 1].map(|day| {
+            let mut fork = analyzer.fork();
+            fork.on_trace(day, 1, &Trace::Message(format!("day {}", day)));
+            fork
+        });
+        for fork in forks {
+            analyzer.merge(fork);
+        }
+        analyzer.after_all();
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let days = written.lines().map(|line| line.split("\"day\": ").nth(1).unwrap()[..1].to_string()).collect::<Vec<_>>();
+        assert_eq!(days, vec!["1", "2"]);
+    }
+}
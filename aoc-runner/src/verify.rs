@@ -0,0 +1,165 @@
+//! Verification of computed answers against an [AnswerStore]
+
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::{
+    analyzer::{Failure, ParallelAnalyzer, Phase},
+    answers::{AnswerStore, Answers, Verdict},
+    Analyzer, Answer, InputDigest, TimeAnalyzer, Trace,
+};
+
+/// An analyzer that compares each part's answer to the recorded one
+///
+/// Wraps another analyzer, forwards all hooks to it and prints a verification table after the
+/// wrapped analyzer's report.
+pub struct Verifier<A> {
+    inner: A,
+    store: AnswerStore,
+    running_all: bool,
+    answers: BTreeMap<usize, Answers>,
+    verdicts: BTreeMap<(usize, usize), Verdict>,
+}
+
+impl<A: Analyzer> Verifier<A> {
+    pub fn new(inner: A, store: AnswerStore) -> Self {
+        Self {
+            inner,
+            store,
+            running_all: false,
+            answers: Default::default(),
+            verdicts: Default::default(),
+        }
+    }
+
+    /// Returns true if any answer did not match its recorded answer
+    pub fn failed(&self) -> bool {
+        self.verdicts.values().any(|it| matches!(it, Verdict::Fail { .. }))
+    }
+
+    fn report(&self) {
+        fn print_col(verdict: Option<&Verdict>) {
+            match verdict {
+                Some(verdict) => print!(" {:>8} |", verdict),
+                None => print!(" {:>8} |", ""),
+            }
+        }
+
+        println!();
+        println!("+--------|----------|----------+");
+        println!("| Day    | Part 1   | Part 2   |");
+        println!("+--------|----------|----------+");
+        for day in self.answers.keys() {
+            print!("| {:>6} |", day);
+            print_col(self.verdicts.get(&(*day, 1)));
+            print_col(self.verdicts.get(&(*day, 2)));
+            println!();
+        }
+        println!("+--------|----------|----------+");
+
+        for ((day, part), verdict) in self.verdicts.iter() {
+            if let Verdict::Fail { expected, actual } = verdict {
+                println!("Day {}, part {}: expected {}, got {}", day, part, expected, actual);
+            }
+        }
+    }
+}
+
+impl<A: Analyzer> Analyzer for Verifier<A> {
+    fn before_all(&mut self) {
+        self.running_all = true;
+        self.inner.before_all();
+    }
+
+    fn after_all(&mut self) {
+        self.inner.after_all();
+        self.report();
+    }
+
+    fn before_day(&mut self, day: usize) {
+        let answers = self.store.load(day).unwrap_or_else(|err| {
+            eprintln!("Could not load answers: {}", err);
+            Answers::default()
+        });
+        self.answers.insert(day, answers);
+        self.inner.before_day(day);
+    }
+
+    fn after_day(&mut self, day: usize) {
+        self.inner.after_day(day);
+        if !self.running_all {
+            self.report();
+        }
+    }
+
+    fn before_parse(&mut self, day: usize) {
+        self.inner.before_parse(day);
+    }
+
+    fn after_parse(&mut self, day: usize) {
+        self.inner.after_parse(day);
+    }
+
+    fn before_prepare(&mut self, day: usize) {
+        self.inner.before_prepare(day);
+    }
+
+    fn after_prepare(&mut self, day: usize) {
+        self.inner.after_prepare(day);
+    }
+
+    fn before_part(&mut self, day: usize, part: usize) {
+        self.inner.before_part(day, part);
+    }
+
+    fn after_part(&mut self, day: usize, part: usize) {
+        self.inner.after_part(day, part);
+    }
+
+    fn on_variant(&mut self, day: usize, part: usize, variant: &str) {
+        self.inner.on_variant(day, part, variant);
+    }
+
+    fn on_trace(&mut self, day: usize, part: usize, trace: &Trace) {
+        self.inner.on_trace(day, part, trace);
+    }
+
+    fn on_answer(&mut self, day: usize, part: usize, answer: &Answer) {
+        let expected = self.answers.get(&day).and_then(|it| it.get(part));
+        self.verdicts.insert((day, part), Verdict::check(expected, answer));
+        self.inner.on_answer(day, part, answer);
+    }
+
+    fn on_input(&mut self, day: usize, input: &InputDigest) {
+        self.inner.on_input(day, input);
+    }
+
+    fn time_analyzer(&mut self) -> Option<&mut TimeAnalyzer> {
+        self.inner.time_analyzer()
+    }
+
+    fn on_failure(&mut self, day: usize, phase: Phase, failure: &Failure) {
+        self.inner.on_failure(day, phase, failure);
+    }
+
+    fn on_timeout(&mut self, day: usize, part: usize, timeout: Duration) {
+        self.inner.on_timeout(day, part, timeout);
+    }
+}
+
+impl<A: ParallelAnalyzer> ParallelAnalyzer for Verifier<A> {
+    fn fork(&self) -> Self {
+        Self {
+            inner: self.inner.fork(),
+            store: self.store.clone(),
+            running_all: self.running_all,
+            answers: Default::default(),
+            verdicts: Default::default(),
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.inner.merge(other.inner);
+        self.answers.extend(other.answers);
+        self.verdicts.extend(other.verdicts);
+    }
+}
@@ -6,9 +6,9 @@ const SAMPLE_SIZE: usize = 100;
 
 macro_rules! get_input {
     ($day: expr) => {{
-        let input_file_path = format!("./input/{}.txt", $day);
-        let input = std::fs::read_to_string(input_file_path).expect("Could not read input file");
-        input
+        use aoc_runner::InputProvider;
+        let input = aoc_runner::InputDir::default().text(aoc2025::days::YEAR, $day).expect("Could not read input file");
+        input.unwrap_or_else(|| panic!("No input for day {}", $day))
     }};
 }
 
@@ -21,23 +21,25 @@ macro_rules! bench_day {
         fn parse(c: &mut Criterion) {
             let input = get_input!($day);
             let mut day = <$Day>::default();
-            let name = format!("day {} - parse", $day);
+            let name = format!("day {:0>2} - parse", $day);
             c.bench_function(&name, |b| b.iter(|| day.parse(black_box(&input))));
         }
 
         fn part1(c: &mut Criterion) {
             let input = get_input!($day);
             let mut day = <$Day>::default();
-            day.parse(&input);
-            let name = format!("day {} - part 1", $day);
+            day.parse(&input).expect("Could not parse input");
+            day.prepare();
+            let name = format!("day {:0>2} - part 1", $day);
             c.bench_function(&name, |b| b.iter(|| black_box(day.part1())));
         }
 
         fn part2(c: &mut Criterion) {
             let input = get_input!($day);
             let mut day = <$Day>::default();
-            day.parse(&input);
-            let name = format!("day {} - part 2", $day);
+            day.parse(&input).expect("Could not parse input");
+            day.prepare();
+            let name = format!("day {:0>2} - part 2", $day);
             c.bench_function(&name, |b| b.iter(|| black_box(day.part2())));
         }
 
@@ -50,15 +52,18 @@ macro_rules! bench_day {
     };
 }
 
-bench_day!("01", day_01, aoc2025::days::day01::Day01);
-bench_day!("02", day_02, aoc2025::days::day02::Day02);
-bench_day!("03", day_03, aoc2025::days::day03::Day03);
-bench_day!("04", day_04, aoc2025::days::day04::Day04);
-bench_day!("05", day_05, aoc2025::days::day05::Day05);
-bench_day!("06", day_06, aoc2025::days::day06::Day06);
-bench_day!("07", day_07, aoc2025::days::day07::Day07);
-bench_day!("08", day_08, aoc2025::days::day08::Day08);
-bench_day!("09", day_09, aoc2025::days::day09::Day09);
+bench_day!(1, day_01, aoc2025::days::day01::Day01);
+bench_day!(2, day_02, aoc2025::days::day02::Day02);
+bench_day!(3, day_03, aoc2025::days::day03::Day03);
+bench_day!(4, day_04, aoc2025::days::day04::Day04);
+bench_day!(5, day_05, aoc2025::days::day05::Day05);
+bench_day!(6, day_06, aoc2025::days::day06::Day06);
+bench_day!(7, day_07, aoc2025::days::day07::Day07);
+bench_day!(8, day_08, aoc2025::days::day08::Day08);
+bench_day!(9, day_09, aoc2025::days::day09::Day09);
+bench_day!(10, day_10, aoc2025::days::day10::Day10);
+bench_day!(11, day_11, aoc2025::days::day11::Day11);
+bench_day!(12, day_12, aoc2025::days::day12::Day12);
 
 criterion::criterion_main!(
     day_01::bench,
@@ -70,4 +75,7 @@ criterion::criterion_main!(
     day_07::bench,
     day_08::bench,
     day_09::bench,
+    day_10::bench,
+    day_11::bench,
+    day_12::bench,
 );
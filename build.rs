@@ -0,0 +1,3 @@
+fn main() {
+    aoc_runner::build::collect_days("src/days", "days.rs");
+}
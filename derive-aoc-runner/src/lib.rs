@@ -2,41 +2,77 @@
 //!
 //! # Example
 //!
+//! ## Registering days
+//! Each day is annotated with its day number:
+//! ```rust
+//! use aoc_runner::Day;
+//! use derive_aoc_runner::aoc;
+//!
+//! #[aoc(day = 7)]
+//! #[derive(Default)]
+//! pub struct Day07 {
+//!     // ...
+//! }
+//!
+//! impl Day<'_> for Day07 {
+//!     type Result1 = u64;
+//!     type Result2 = u64;
+//!
+//!     fn part1(&mut self) -> Self::Result1 {
+//!         0
+//!     }
+//!
+//!     fn part2(&mut self) -> Self::Result2 {
+//!         0
+//!     }
+//! }
+//! ```
+//!
+//! The build script collects all annotated days into a [Registry][aoc_runner::Registry], see
+//! [collect_days][aoc_runner::build::collect_days]:
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     aoc_runner::build::collect_days("src/days", "days.rs");
+//! }
+//!
+//! // src/days/mod.rs
+//! pub mod day07;
+//!
+//! include!(concat!(env!("OUT_DIR"), "/days.rs"));
+//! ```
+//!
 //! ## Using the default [TimeAnalyzer][aoc_runner::TimeAnalyzer]
 //! ```rust
-//! use aoc_runner::{Day,Analyzer};
-//! use derive_aoc_runner::{AoC, Analyzer};
+//! use aoc_runner::{Analyzer, Registry};
+//! use derive_aoc_runner::Analyzer;
 //!
 //! #[derive(Analyzer)]
-//! #[derive(AoC)]
-//! pub(crate) struct Days(
-//!   day01::Day01,
-//!   day02::Day02,
-//!   day03::Day03,
-//!   day04::Day04,
-//!   day05::Day05,
-//!   day06::Day06,
-//! );
+//! pub(crate) struct Days(Registry);
+//! ```
+//!
+//! ## Combining analyzers
+//! The analyzers named by the `analyzer` attribute all follow the same run, see
+//! [compose][aoc_runner::compose]. Their [TimeAnalyzer][aoc_runner::TimeAnalyzer], if any, is
+//! configured through [time_analyzer][aoc_runner::Analyzer::time_analyzer]:
+//! ```ignore
+//! use aoc_runner::{AllocAnalyzer, Registry, TimeAnalyzer};
+//! use derive_aoc_runner::Analyzer;
+//!
+//! #[derive(Analyzer)]
+//! #[analyzer(AllocAnalyzer, TimeAnalyzer)]
+//! pub(crate) struct Days(Registry);
 //! ```
 //!
 //! ## Using a custom Analyzer
 //! ```rust
-//! use aoc_runner::{Day,Analyzer};
-//! use derive_aoc_runner::{AoC, Analyzer};
+//! use aoc_runner::{Analyzer, Registry};
 //!
 //! struct MyAnalyzer;
 //!
 //! impl Analyzer for MyAnalyzer {}
 //!
-//! #[derive(AoC)]
-//! pub(crate) struct Days(
-//!   day01::Day01,
-//!   day02::Day02,
-//!   day03::Day03,
-//!   day04::Day04,
-//!   day05::Day05,
-//!   day06::Day06,
-//! );
+//! pub(crate) struct Days(Registry);
 //!
 //! impl Days {
 //!     fn get_analyzer(&self) -> impl Analyzer {
@@ -46,221 +82,123 @@
 //! ```
 //!
 //! ## Run puzzles
-//! ```rust
-//! let runner = Days::new(); // creates new runner with Default::default() values for puzzles
-//! runner.run_day( 1 /* day */,               "" /* input */, &mut runner.get_analyzer());
-//! runner.run_part(1 /* day */, 2 /* part */, "" /* input */, &mut runner.get_analyzer());
-//! runner.run_all(["", ""] /* inputs */,                      &mut runner.get_analyzer());
+//! ```no_run
+//! # use aoc_runner::{AnswerStore, Registry, RunOptions, TimeAnalyzer, Verifier};
+//! # fn registry() -> Registry { Registry::new() }
+//! let mut registry = registry(); // registry of all days, with Default::default() values for puzzles
+//! let mut analyzer = TimeAnalyzer::new();
+//! let options = RunOptions::default(); // e.g. a timeout for each part
+//! registry.run_day( 1 /* day */,               "" /* input, or Input::stream(reader) */, &options, &mut analyzer);
+//! registry.run_part(1 /* day */, Some(2) /* part */, "" /* input */, &options, &mut analyzer);
+//! registry.run_some(vec![Some("".into()), None] /* inputs */, &options, &mut analyzer);
+//! registry.run_examples(Some(1) /* day, or None for all days */);
+//!
+//! // verifies answers against `./answers/NN.toml`
+//! let mut analyzer = Verifier::new(analyzer, AnswerStore::new("./answers"));
+//! registry.run_some(vec![Some("".into()), None] /* inputs */, &options, &mut analyzer);
 //! ```
 //!
 
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{parse_macro_input, Data, DeriveInput, Fields, FieldsUnnamed};
+use syn::{parse_macro_input, AttributeArgs, Data, DeriveInput, Lit, Meta, NestedMeta};
 
-/// Derives multiple utility methods for running Advent of Code puzzles on a struct
+/// Registers a struct implementing [Day][aoc_runner::Day] as the puzzle of a day
 ///
-/// Derives
-/// - `fn new() -> Self`
-/// - `fn len(&self) -> usize`
-/// - `fn is_empty(&self) -> bool`
-/// - `fn run_day(&self, day: usize, input: &str, analyzer: &mut impl Analyzer)`
-/// - `fn run_part(&self, day: usize, part: usize, input: &str, analyzer: &mut impl Analyzer)`
-/// - `fn run_all<I: AsRef<str>>(&mut self, inputs: &[I])`
-#[proc_macro_derive(AoC)]
-pub fn derive_aoc(input: TokenStream) -> TokenStream {
-    let DeriveInput { ident, data, .. } = parse_macro_input!(input);
-
-    let usage = format!(
-        "#[derive(AoC)] can currently only be applied to structs with unnamend fields.
-
-Also, alls fields must implement aoc_runner::Day + Default.
-
-Example:
-#[derive(Aoc)]
-struct Aoc2022(
-    day01::Day01,
-    day02::Day02,
-);"
-    );
-
-    if let Data::Struct(s) = data {
-        if let Fields::Unnamed(FieldsUnnamed { unnamed, .. }) = s.fields {
-            let fields_len = unnamed.len();
-            let fields_ty = unnamed.iter().map(|f| &f.ty);
-            let field_indices = (0..fields_len).map(syn::Index::from);
-
-            let new_impl = {
-                let doc = format!(
-                    "Creates a new [{}], initialized with default values for all days",
-                    ident
-                );
-                quote! {
-                    #[doc = #doc]
-                    pub fn new() -> Self {
-                        Self(#(<#fields_ty>::default(),)*)
-                    }
-                }
-            };
-
-            let len_impl = {
-                let doc = "Return the number of available days";
-                quote! {
-                    #[doc = #doc]
-                    pub fn len(&self) -> usize {
-                        #fields_len
-                    }
-                }
-            };
-
-            let is_empty_impl = {
-                let doc = "Return true if the container hosts no days";
-                quote! {
-                    #[doc = #doc]
-                    pub fn is_empty(&self) -> bool {
-                        #fields_len == 0
-                    }
-                }
-            };
-
-            let run_day_impl = {
-                let doc = "Runs both parts of a given day";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_day(&mut self, day: usize, input: &str, analyzer: &mut impl Analyzer) {
-                        self.run_part(day, None, input, analyzer);
-                    }
-                }
-            };
-
-            let run_part_impl = {
-                let doc = "Runs one or both parts of a given day";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_part(&mut self, day: usize, part: Option<usize>, input: &str, mut analyzer: &mut impl Analyzer) {
-                        match day - 1 {
-                            #( #field_indices => {
-                                println!("Day {}", day);
-
-                                analyzer.before_day(day);
-                                analyzer.before_parse(day);
-                                self.#field_indices.parse(input);
-                                analyzer.after_parse(day);
-                                match part {
-                                    Some(1) => {
-                                        analyzer.before_part(day, 1);
-                                        let result = self.#field_indices.part1();
-                                        analyzer.after_part(day, 1);
-                                        self.#field_indices.print_part1(result);
-                                    }
-                                    Some(2) => {
-                                        analyzer.before_part(day, 2);
-                                        let result = self.#field_indices.part2();
-                                        analyzer.after_part(day, 2);
-                                        self.#field_indices.print_part2(result);
-                                    }
-                                    None => {
-                                        analyzer.before_part(day, 1);
-                                        let result = self.#field_indices.part1();
-                                        analyzer.after_part(day, 1);
-                                        self.#field_indices.print_part1(result);
-
-                                        analyzer.before_part(day, 2);
-                                        let result = self.#field_indices.part2();
-                                        analyzer.after_part(day, 2);
-                                        self.#field_indices.print_part2(result);
-                                    }
-                                    Some(part) => panic!("Invalid part: {}. Valid parts are: 1,2", part)
-                                }
-                                analyzer.after_day(day);
-                            } )*
-                            _ => panic!("Invalid day: {}. Valid days are 1..{}", day, #fields_len)
-                        }
-                    }
-                }
-            };
-
-            let run_all_impl = {
-                let doc = "Runs both parts of all available days";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_all<I: AsRef<str>>(&mut self, inputs: &[I]) {
-                        assert_eq!(inputs.len(), self.len());
-
-                        let mut analyzer = self.get_analyzer();
-
-                        analyzer.before_all();
-                        for i in 1..=self.len() {
-                            let input = inputs[i - 1].as_ref();
-                            self.run_day(i, input, &mut analyzer);
-                        }
-                        analyzer.after_all();
-                    }
-                }
-            };
-
-            let run_some_impl = {
-                let doc = "Runs both parts for all days where an input is given";
-                quote! {
-                    #[doc = #doc]
-                    pub fn run_some<I: AsRef<str>>(&mut self, inputs: &[Option<I>]) {
-                        assert_eq!(inputs.len(), self.len());
-
-                        let mut analyzer = self.get_analyzer();
+/// Implements [Puzzle][aoc_runner::Puzzle] with the given day number. Generic parameters of the
+/// struct must have defaults, which are used when running the day. A lifetime is the one of the
+/// input the day borrows from, e.g. `Day11<'input>`.
+///
+/// # Example
+/// ```ignore
+/// #[aoc(day = 7)]
+/// #[derive(Default)]
+/// pub struct Day07;
+/// ```
+#[proc_macro_attribute]
+pub fn aoc(args: TokenStream, input: TokenStream) -> TokenStream {
+    let usage = "#[aoc] expects the day number of the puzzle, e.g. #[aoc(day = 7)]";
+
+    let args = parse_macro_input!(args as AttributeArgs);
+    let item = parse_macro_input!(input as DeriveInput);
+    if !matches!(item.data, Data::Struct(_)) {
+        panic!("#[aoc] can only be applied to structs");
+    }
 
-                        analyzer.before_all();
-                        for i in 1..=self.len() {
-                            if let Some(input) = &inputs[i - 1] {
-                                self.run_day(i, input.as_ref(), &mut analyzer);
-                            }
-                        }
-                        analyzer.after_all();
-                    }
-                }
-            };
+    let day = match &args[..] {
+        [NestedMeta::Meta(Meta::NameValue(meta))] if meta.path.is_ident("day") => match &meta.lit {
+            Lit::Int(day) => day.base10_parse::<usize>().unwrap_or_else(|_| panic!("{}", usage)),
+            _ => panic!("{}", usage),
+        },
+        _ => panic!("{}", usage),
+    };
+    if !(1..=aoc_runner::registry::LAST_DAY).contains(&day) {
+        panic!("Invalid day: {}. Valid days are 1..{}", day, aoc_runner::registry::LAST_DAY);
+    }
 
-            let output = quote! {
-                impl #ident {
-                    #new_impl
-                    #len_impl
-                    #is_empty_impl
-                    #run_day_impl
-                    #run_part_impl
-                    #run_all_impl
-                    #run_some_impl
-                }
-            };
+    let ident = &item.ident;
+    let lifetimes = item.generics.lifetimes().map(|_| quote! { 'static });
+    let input_lifetimes = item.generics.lifetimes().map(|_| quote! { 'input });
+    let output = quote! {
+        #item
 
-            // panic!("{}", output.to_string());
-            output.into()
-        } else {
-            panic!("{}", usage)
+        impl ::aoc_runner::Puzzle for #ident<#(#lifetimes),*> {
+            const DAY: usize = #day;
+            type Day<'input> = #ident<#(#input_lifetimes),*>;
         }
-    } else {
-        panic!("{}", usage)
-    }
+    };
+
+    output.into()
 }
 
 /// Derives a method for this struct that returns an [Analyzer][aoc_runner::Analyzer] instance
 ///
 /// Derives
-/// - `fn get_analyzer(&self) -> impl Analyzer`
-#[proc_macro_derive(Analyzer)]
+/// - `fn get_analyzer(&self) -> TimeAnalyzer`
+///
+/// The optional `analyzer` attribute names the analyzers to use instead, which must implement
+/// [Default]. Several analyzers are combined into a tuple, e.g. `#[analyzer(AllocAnalyzer, TimeAnalyzer)]`
+/// derives `fn get_analyzer(&self) -> (AllocAnalyzer, TimeAnalyzer)`. The tuple only runs days in
+/// parallel if all of its analyzers implement [ParallelAnalyzer][aoc_runner::ParallelAnalyzer].
+#[proc_macro_derive(Analyzer, attributes(analyzer))]
 pub fn derive_get_analyzer(input: TokenStream) -> TokenStream {
-    let DeriveInput { ident, .. } = parse_macro_input!(input);
+    let usage = "#[analyzer] expects the types of the analyzers, e.g. #[analyzer(AllocAnalyzer, TimeAnalyzer)]";
+
+    let DeriveInput { ident, attrs, .. } = parse_macro_input!(input);
+
+    let mut analyzers = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("analyzer")) {
+        let Ok(Meta::List(list)) = attr.parse_meta() else {
+            panic!("{}", usage);
+        };
+        for nested in list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::Path(path)) => analyzers.push(path),
+                _ => panic!("{}", usage),
+            }
+        }
+    }
+
+    let (ty, value) = match &analyzers[..] {
+        [] => (quote! { ::aoc_runner::TimeAnalyzer }, quote! { ::aoc_runner::TimeAnalyzer::new() }),
+        [analyzer] => (quote! { #analyzer }, quote! { <#analyzer as ::core::default::Default>::default() }),
+        analyzers => (
+            quote! { (#(#analyzers),*) },
+            quote! { (#(<#analyzers as ::core::default::Default>::default()),*) },
+        ),
+    };
 
     let get_analyzer_impl = {
         let doc = "Creates a new analyzer to use during execution";
         quote! {
             #[doc = #doc]
-            pub fn get_analyzer(&self) -> impl Analyzer {
-                TimeAnalyzer::new()
+            pub fn get_analyzer(&self) -> #ty {
+                #value
             }
         }
     };
 
     let output = quote! {
-        use aoc_runner::{TimeAnalyzer};
         impl #ident {
             #get_analyzer_impl
         }
@@ -1,15 +1,26 @@
+/// Prints in debug builds, or sends a trace message if the running part is traced
 #[macro_export]
 macro_rules! debug {
     ($str: expr) => {
         #[cfg(debug_assertions)]
         {
-            println!($str)
+            let tracer = aoc_runner::Tracer::current();
+            if tracer.is_active() {
+                tracer.trace(format!($str))
+            } else {
+                println!($str)
+            }
         }
     };
     ($str: expr, $($rest: expr),+) => {
         #[cfg(debug_assertions)]
         {
-            println!($str, $($rest),+)
+            let tracer = aoc_runner::Tracer::current();
+            if tracer.is_active() {
+                tracer.trace(format!($str, $($rest),+))
+            } else {
+                println!($str, $($rest),+)
+            }
         }
     };
 }
@@ -1,27 +1,38 @@
 //! # Day 01 Secret Entrance
 
-use aoc_runner::Day;
+use aoc_runner::{parse_at, Day, Example, ParseError};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 
+#[aoc(day = 1)]
 #[derive(Default, Clone)]
 pub struct Day01 {
     instructions: Vec<i16>,
 }
 
-impl Day for Day01 {
+impl Day<'_> for Day01 {
     type Result1 = u16;
     type Result2 = u16;
 
-    fn parse(&mut self, input: &str) {
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("3"), Some("6"))];
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         self.instructions = input
             .lines()
-            .into_iter()
             .filter(|it| !it.is_empty())
             .map(|line| {
-                let (dir, num) = line.split_at(1);
-                let int: i16 = num.parse().unwrap();
-                if dir == "L" { -int } else { int }
+                let (dir, num) = line.split_at_checked(1)
+                    .ok_or_else(|| ParseError::at(input, line, "'L' or 'R'"))?;
+                let int: i16 = parse_at(input, num, "a number")?;
+                match dir {
+                    "L" => Ok(-int),
+                    "R" => Ok(int),
+                    _ => Err(ParseError::at(input, dir, "'L' or 'R'")),
+                }
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -54,35 +65,22 @@ impl Day for Day01 {
     }
 }
 
+const EXAMPLE: &str = indoc! {"
+    L68
+    L30
+    R48
+    L5
+    R60
+    L55
+    L1
+    L99
+    R14
+    L82
+"};
+
 #[cfg(test)]
 mod test {
     use super::*;
-    use indoc::indoc;
-
-    const INPUT: &str = indoc! {"
-        L68
-        L30
-        R48
-        L5
-        R60
-        L55
-        L1
-        L99
-        R14
-        L82
-    "};
 
-    #[test]
-    fn part_1() {
-        let mut day = Day01::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 3);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day01::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 6);
-    }
+    aoc_runner::example_tests!(Day01);
 }
@@ -2,27 +2,42 @@
 
 use std::{collections::HashSet, iter, ops::Range};
 
-use aoc_runner::Day;
+use aoc_runner::{parse_at, Day, Example, ParseError, Variant};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 
+#[aoc(day = 2)]
 #[derive(Default, Clone)]
 pub struct Day02 {
     ranges: Vec<Range<u64>>
 }
 
-impl Day for Day02 {
+impl Day<'_> for Day02 {
     type Result1 = u64;
     type Result2 = u64;
 
-    fn parse(&mut self, input: &str) {
-        self.ranges = input.lines().nth(0).unwrap()
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("1227775554"), Some("4174379265"))];
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.ranges = input.lines().next()
+            .ok_or_else(|| ParseError::eof(input, "a list of ranges"))?
             .split(",")
-            .into_iter()
             .map(|pair| {
-                let (lo, hi) = pair.split_once("-").unwrap();
-                let (lo, hi) = (lo.parse().unwrap(), hi.parse::<u64>().unwrap());
-                lo..(hi + 1)
+                let (lo, hi) = pair.split_once("-").ok_or_else(|| ParseError::at(input, pair, "a range 'lo-hi'"))?;
+                let (lo, hi): (u64, u64) = (parse_at(input, lo, "a number")?, parse_at(input, hi, "a number")?);
+                Ok(lo..(hi + 1))
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
+    }
+
+    fn variants1() -> Vec<Variant<Self, Self::Result1>> {
+        vec![("naive", Self::part1_naive)]
+    }
+
+    fn variants2() -> Vec<Variant<Self, Self::Result2>> {
+        vec![("naive", Self::part2_naive)]
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -34,6 +49,28 @@ impl Day for Day02 {
     }
 }
 
+impl Day02 {
+    /// Checks every id instead of generating the invalid ones
+    fn part1_naive(&mut self) -> u64 {
+        self.ranges.iter().cloned().flatten().filter(|id| is_invalid_id(*id, true)).sum()
+    }
+
+    /// Checks every id instead of generating the invalid ones
+    fn part2_naive(&mut self) -> u64 {
+        self.ranges.iter().cloned().flatten().filter(|id| is_invalid_id(*id, false)).sum()
+    }
+}
+
+/// Returns true if `id` consists of a sequence of digits repeated at least twice (exactly twice if
+/// `twice` is set)
+fn is_invalid_id(id: u64, twice: bool) -> bool {
+    let digits = id.to_string().into_bytes();
+    let len = digits.len();
+    (1..len)
+        .filter(|pattern_len| len.is_multiple_of(*pattern_len) && (!twice || len == 2 * pattern_len))
+        .any(|pattern_len| digits.chunks(pattern_len).all(|chunk| chunk == &digits[..pattern_len]))
+}
+
 fn find_invalid_ids_v1(range: &Range<u64>) -> Vec<u64> {
     let mut result = vec![];
     let (lo, hi) = (range.start, range.end);
@@ -113,12 +150,13 @@ fn repeat_until(i: u64, exp: u64, lo: u64, hi: u64) -> impl Iterator<Item = u64>
         .skip_while(move |it| *it < lo)
 }
 
+const EXAMPLE: &str = indoc! {"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124"};
+    aoc_runner::example_tests!(Day02);
 
     #[test]
     fn invalid_id_v1() {
@@ -134,18 +172,4 @@ mod test {
         assert_eq!(find_invalid_ids_v2(&(95..115)).into_iter().collect::<HashSet<_>>(), HashSet::from([99, 111]));
         assert_eq!(find_invalid_ids_v2(&(45515..60929)).into_iter().collect::<HashSet<_>>(), HashSet::from([55555]));
     }
-
-    #[test]
-    fn part_1() {
-        let mut day = Day02::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 1227775554);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day02::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 4174379265);
-    }
 }
@@ -1,24 +1,36 @@
 //! # Day 03 Lobby
 
-use aoc_runner::Day;
+use aoc_runner::{Day, Example, ParseError};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
+#[aoc(day = 3)]
 #[derive(Default, Clone)]
 pub struct Day03 {
     batteries: Vec<Vec<u8>>
 }
 
-impl Day for Day03 {
+impl Day<'_> for Day03 {
     type Result1 = u32;
     type Result2 = u64;
 
-    fn parse(&mut self, input: &str) {
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("357"), Some("3121910778619"))];
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         self.batteries = input
             .lines()
             .map(|line| {
-                line.chars().map(|c| c as u8 - '0' as u8).collect()
+                line.char_indices().map(|(idx, c)| {
+                    c.to_digit(10)
+                        .map(|digit| digit as u8)
+                        .ok_or_else(|| ParseError::at(input, &line[idx..idx + c.len_utf8()], "a digit"))
+                })
+                .collect()
             })
-            .collect()
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -79,29 +91,16 @@ fn to_num_skipping(digits: &[u8], index_to_skip: usize) -> u64 {
     })
 }
 
+const EXAMPLE: &str = indoc! {"
+    987654321111111
+    811111111111119
+    234234234234278
+    818181911112111
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        987654321111111
-        811111111111119
-        234234234234278
-        818181911112111
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day03::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 357);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day03::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 3121910778619);
-    }
+    aoc_runner::example_tests!(Day03);
 }
@@ -4,38 +4,49 @@ use std::collections::VecDeque;
 
 use fxhash::FxHashSet as HashSet;
 use fxhash::FxHashMap as HashMap;
-use aoc_runner::Day;
+use aoc_runner::{Day, Example, ParseError};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 
 type I = i16;
 type Maze = HashMap<(I, I), u8>;
 
+#[aoc(day = 4)]
 #[derive(Default, Clone)]
 pub struct Day04 {
     maze: Maze
 }
 
-impl Day for Day04 {
+impl Day<'_> for Day04 {
     type Result1 = usize;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
-        let keys = input
-            .lines().enumerate()
-            .flat_map(|(y, line)|
-                line.chars().enumerate().map(move |(x, c)| ((y,x), c))
-            )
-            .filter_map(|((y, x), c)| if c == '@' {
-                Some((y as I, x as I))
-            } else {
-                None
-            })
-            .collect::<HashSet<_>>();
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("13"), Some("43"))];
+
+    // part 2 removes rolls from the maze
+    const FRESH_PARTS: bool = true;
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        let mut keys: HashSet<_> = Default::default();
+        for (y, line) in input.lines().enumerate() {
+            for (x, (idx, c)) in line.char_indices().enumerate() {
+                match c {
+                    '@' => {
+                        keys.insert((y as I, x as I));
+                    }
+                    '.' => {}
+                    _ => return Err(ParseError::at(input, &line[idx..idx + c.len_utf8()], "'@' or '.'")),
+                }
+            }
+        }
 
         self.maze = keys.iter().map(|it| {
             let value = neighbours8(*it).into_iter().filter(|n| keys.contains(n)).count();
             (*it, value as u8)
         })
         .collect();
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -77,35 +88,22 @@ fn neighbours8((y, x): (I, I)) -> [(I, I); 8] {
     ]
 }
 
+const EXAMPLE: &str = indoc! {"
+    ..@@.@@@@.
+    @@@.@.@.@@
+    @@@@@.@.@@
+    @.@@@@..@.
+    @@.@@@@.@@
+    .@@@@@@@.@
+    .@.@.@.@@@
+    @.@@@.@@@@
+    .@@@@@@@@.
+    @.@.@@@.@.
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        ..@@.@@@@.
-        @@@.@.@.@@
-        @@@@@.@.@@
-        @.@@@@..@.
-        @@.@@@@.@@
-        .@@@@@@@.@
-        .@.@.@.@@@
-        @.@@@.@@@@
-        .@@@@@@@@.
-        @.@.@@@.@.
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day04::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 13);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day04::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 43);
-    }
+    aoc_runner::example_tests!(Day04);
 }
@@ -1,27 +1,91 @@
 //! # Day 05 Cafeteria
 
-use aoc_runner::Day;
+use std::io::BufRead;
+
+use aoc_runner::{numbered_lines, parse_at, parse_in_line, Day, Example, ParseError, StreamParser};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 use itertools::Itertools;
 
 type I = u128;
 type Range = (I, I);
 
+#[aoc(day = 5)]
 #[derive(Default, Clone)]
 pub struct Day05 {
     ranges: Vec<Range>,
     ids: Vec<I>,
 }
 
-impl Day for Day05 {
+impl Day<'_> for Day05 {
     type Result1 = usize;
     type Result2 = u128;
 
-    fn parse(&mut self, input: &str) {
-        let (ranges, ids) = input.split_once("\n\n").unwrap();
-        self.ranges = ranges.lines().map(|line| {
-            let (lo, hi) = line.split_once("-").unwrap();
-            (lo.parse::<I>().unwrap(), hi.parse::<I>().unwrap())
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("3"), Some("14"))];
+
+    const STREAMING: Option<StreamParser<Self>> = Some(Self::parse_stream);
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        let (ranges, ids) = input.split_once("\n\n")
+            .ok_or_else(|| ParseError::eof(input, "a blank line between ranges and ids"))?;
+        let ranges = ranges.lines().enumerate()
+            .map(|(idx, line)| parse_range(idx + 1, line))
+            .collect::<Result<_, _>>()?;
+        self.ranges = merge(ranges);
+        self.ids = ids.lines().map(|it| parse_at(input, it, "a number")).collect::<Result<_, _>>()?;
+
+        Ok(())
+    }
+
+    fn part1(&mut self) -> Self::Result1 {
+        self.ids.iter().filter(|it| {
+            self.ranges.iter().any(|r| contains(r, **it))
+        }).count()
+    }
+
+    fn part2(&mut self) -> Self::Result2 {
+        self.ranges.iter().map(|(lo, hi)| hi - lo + 1).sum::<I>() as Self::Result2
+    }
+}
+
+impl Day05 {
+    /// Parses the input line by line while it is read, see [Day::STREAMING]
+    fn parse_stream(&mut self, input: &mut dyn BufRead) -> Result<(), ParseError> {
+        let mut lines = numbered_lines(input);
+        let mut ranges = vec![];
+        let mut line_count = 0;
+        loop {
+            let Some(line) = lines.next() else {
+                return Err(ParseError::in_line(line_count + 1, "", "", "a blank line between ranges and ids"));
+            };
+            let (line_no, line) = line?;
+            if line.is_empty() {
+                break;
+            }
+            ranges.push(parse_range(line_no, &line)?);
+            line_count = line_no;
+        }
+
+        self.ranges = merge(ranges);
+        self.ids = lines.map(|line| {
+            let (line_no, line) = line?;
+            parse_in_line(line_no, &line, &line, "a number")
         })
+        .collect::<Result<_, _>>()?;
+
+        Ok(())
+    }
+}
+
+/// Parses the range on the `line_no`th line, e.g. `10-14`
+fn parse_range(line_no: usize, line: &str) -> Result<Range, ParseError> {
+    let (lo, hi) = line.split_once("-").ok_or_else(|| ParseError::in_line(line_no, line, line, "a range 'lo-hi'"))?;
+    Ok((parse_in_line::<I>(line_no, line, lo, "a number")?, parse_in_line::<I>(line_no, line, hi, "a number")?))
+}
+
+/// Sorts `ranges` and merges overlapping ones
+fn merge(ranges: Vec<Range>) -> Vec<Range> {
+    ranges.into_iter()
         .sorted_by_key(|r| (r.0, r.1))
         .fold(vec![], |mut acc, el| {
             if acc.is_empty() {
@@ -37,19 +101,7 @@ impl Day for Day05 {
             }
 
             acc
-        });
-        self.ids = ids.lines().map(|it| it.parse().unwrap()).collect();
-    }
-
-    fn part1(&mut self) -> Self::Result1 {
-        self.ids.iter().filter(|it| {
-            self.ranges.iter().any(|r| contains(r, **it))
-        }).count()
-    }
-
-    fn part2(&mut self) -> Self::Result2 {
-        self.ranges.iter().map(|(lo, hi)| hi - lo + 1).sum::<I>() as Self::Result2
-    }
+        })
 }
 
 #[inline]
@@ -57,36 +109,23 @@ fn contains(r: &Range, i: I) -> bool {
     i >= r.0 && i <= r.1
 }
 
+const EXAMPLE: &str = indoc! {"
+    3-5
+    10-14
+    16-20
+    12-18
+
+    1
+    5
+    8
+    11
+    17
+    32
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        3-5
-        10-14
-        16-20
-        12-18
-
-        1
-        5
-        8
-        11
-        17
-        32
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day05::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 3);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day05::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 14);
-    }
+    aoc_runner::example_tests!(Day05);
 }
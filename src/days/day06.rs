@@ -2,7 +2,9 @@
 
 use std::vec;
 
-use aoc_runner::Day;
+use aoc_runner::{Day, Example, ParseError};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 
 type I = u64;
 type Matrix = Vec<Vec<I>>;
@@ -14,37 +16,31 @@ enum Op {
     Mul
 }
 
+#[aoc(day = 6)]
 #[derive(Default, Clone)]
-pub struct Day06 {
-    input: String,
+pub struct Day06<'input> {
+    /// Rows of numbers, as they appear in the input
+    nums: Vec<&'input str>,
+    /// Operators of the columns
+    ops: Ops,
+    /// Numbers read row by row, shared by part 1
+    rows: Matrix,
+    /// Numbers read column by column from the right, shared by part 2
+    columns: Matrix,
 }
 
-impl Day06 {
-    fn parse_matrix(&mut self) -> (Matrix, Ops) {
-        let lines = self.input.lines().collect::<Vec<_>>();
-        let nums = &lines[0..lines.len() - 1];
-        let ops = &lines[lines.len() - 1];
-
-        let matrix = nums.iter().map(|line| {
+impl Day06<'_> {
+    fn parse_matrix(&self) -> Matrix {
+        self.nums.iter().map(|line| {
             line.split_whitespace().map(|n| n.parse().unwrap()).collect()
         })
-        .collect();
-        let ops = ops.split_whitespace().map(|op| match op {
-            "+" => Op::Add,
-            "*" => Op::Mul,
-            _ => unreachable!()
-        })
-        .collect();
-
-        (matrix, ops)
+        .collect()
     }
 
-    fn parse_matrix_transposed(&mut self) -> (Matrix, Ops) {
-        let lines = self.input.lines().collect::<Vec<_>>();
-        let nums = &lines[0..lines.len() - 1];
-        let ops = &lines[lines.len() - 1];
+    fn parse_matrix_transposed(&self) -> Matrix {
+        let nums = &self.nums;
 
-        let matrix = {
+        {
             let width = nums.iter().map(|it| it.len()).max().unwrap();
             let mut result = vec![vec![]];
             for col in (0..width).rev() {
@@ -67,20 +63,10 @@ impl Day06 {
 
             }
             result
-        };
-
-        let ops = ops.split_whitespace().map(|op| match op {
-            "+" => Op::Add,
-            "*" => Op::Mul,
-            _ => unreachable!()
-        })
-        .rev()
-        .collect();
-
-        (matrix, ops)
+        }
     }
 
-    fn sum_matrix(matrix: Matrix, ops: Ops) -> I {
+    fn sum_matrix(matrix: &Matrix, ops: &Ops) -> I {
         let len = matrix[0].len();
         let mut results = matrix[0].clone();
         for col in 0..len {
@@ -99,12 +85,12 @@ impl Day06 {
         results.into_iter().sum()
     }
 
-    fn sum_matrix_transposed(matrix: Matrix, ops: Ops) -> I {
-        matrix.into_iter().enumerate()
-            .map(|(idx, nums)| {
-                match ops[idx] {
-                  Op::Add => nums.into_iter().sum::<I>(),
-                  Op::Mul => nums.into_iter().product::<I>(),
+    fn sum_matrix_transposed(matrix: &Matrix, ops: &Ops) -> I {
+        matrix.iter().zip(ops.iter().rev())
+            .map(|(nums, op)| {
+                match op {
+                  Op::Add => nums.iter().sum::<I>(),
+                  Op::Mul => nums.iter().product::<I>(),
                 }
             })
             .sum()
@@ -112,48 +98,61 @@ impl Day06 {
 
 }
 
-impl Day for Day06 {
+impl<'input> Day<'input> for Day06<'input> {
     type Result1 = I;
     type Result2 = I;
 
-    fn parse(&mut self, input: &str) {
-        self.input = input.to_string();
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("4277556"), Some("3263827"))];
+
+    const PREPARE: bool = true;
+
+    fn parse(&mut self, input: &'input str) -> Result<(), ParseError> {
+        let lines = input.lines().collect::<Vec<_>>();
+        let Some((ops, nums @ [_, ..])) = lines.split_last() else {
+            return Err(ParseError::eof(input, "rows of numbers followed by a row of operators"));
+        };
+
+        for line in nums {
+            if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_digit() && *c != ' ') {
+                return Err(ParseError::at(input, &line[idx..idx + c.len_utf8()], "a digit or ' '"));
+            }
+        }
+        self.ops = ops.split_whitespace().map(|op| match op {
+            "+" => Ok(Op::Add),
+            "*" => Ok(Op::Mul),
+            _ => Err(ParseError::at(input, op, "'+' or '*'")),
+        })
+        .collect::<Result<_, _>>()?;
+        self.nums = nums.to_vec();
+
+        Ok(())
+    }
+
+    /// Reads the numbers row by row and column by column
+    fn prepare(&mut self) {
+        self.rows = self.parse_matrix();
+        self.columns = self.parse_matrix_transposed();
     }
 
     fn part1(&mut self) -> Self::Result1 {
-        let (matrix, ops) = self.parse_matrix();
-        Self::sum_matrix(matrix, ops)
+        Self::sum_matrix(&self.rows, &self.ops)
     }
 
     fn part2(&mut self) -> Self::Result2 {
-        let (matrix, ops) = self.parse_matrix_transposed();
-        Self::sum_matrix_transposed(matrix, ops)
+        Self::sum_matrix_transposed(&self.columns, &self.ops)
     }
 }
 
+const EXAMPLE: &str = indoc! {"
+    123 328  51 64
+     45 64  387 23
+      6 98  215 314
+    *   +   *   +
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        123 328  51 64
-         45 64  387 23
-          6 98  215 314
-        *   +   *   +
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day06::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 4277556);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day06::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 3263827);
-    }
+    aoc_runner::example_tests!(Day06);
 }
@@ -4,12 +4,15 @@ use std::collections::VecDeque;
 
 use fxhash::FxHashSet as HashSet;
 use fxhash::FxHashMap as HashMap;
-use aoc_runner::Day;
+use aoc_runner::{Day, Example, ParseError};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 
 type I = i16;
 type C = (I, I);
 type Splitters = HashSet<C>;
 
+#[aoc(day = 7)]
 #[derive(Default, Clone)]
 pub struct Day07 {
     width: I,
@@ -18,28 +21,37 @@ pub struct Day07 {
     splitters: Splitters,
 }
 
-impl Day for Day07 {
+impl Day<'_> for Day07 {
     type Result1 = u32;
     type Result2 = u64;
 
-    fn parse(&mut self, input: &str) {
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("21"), Some("40"))];
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         let tiles = input.lines().enumerate()
             .flat_map(|(y, line)| {
-                line.chars().enumerate().map(move |(x, c)| {
-                    ((y as I, x as I), c)
+                line.char_indices().enumerate().map(move |(x, (idx, c))| {
+                    ((y as I, x as I), c, &line[idx..idx + c.len_utf8()])
                 })
             });
 
-        for (coord@(y, x), char) in tiles {
-            if char == 'S' {
-                self.start = coord;
-            } else if char == '^' {
-                self.splitters.insert(coord);
+        for (coord@(y, x), char, text) in tiles {
+            match char {
+                'S' => {
+                    self.start = coord;
+                }
+                '^' => {
+                    self.splitters.insert(coord);
+                }
+                '.' => {}
+                _ => return Err(ParseError::at(input, text, "'.', '^' or 'S'")),
             }
 
             self.width = self.width.max(x);
             self.height = self.height.max(y);
         }
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -69,7 +81,7 @@ impl Day for Day07 {
     }
 
     fn part2(&mut self) -> Self::Result2 {
-        type ResultType = <crate::days::day07::Day07 as Day>::Result2;
+        type ResultType = <crate::days::day07::Day07 as Day<'static>>::Result2;
         type Cache = HashMap<C, ResultType>;
         let mut cache: Cache = Default::default();
 
@@ -116,41 +128,28 @@ impl Day for Day07 {
 
 }
 
+const EXAMPLE: &str = indoc! {"
+    .......S.......
+    ...............
+    .......^.......
+    ...............
+    ......^.^......
+    ...............
+    .....^.^.^.....
+    ...............
+    ....^.^...^....
+    ...............
+    ...^.^...^.^...
+    ...............
+    ..^...^.....^..
+    ...............
+    .^.^.^.^.^...^.
+    ...............
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        .......S.......
-        ...............
-        .......^.......
-        ...............
-        ......^.^......
-        ...............
-        .....^.^.^.....
-        ...............
-        ....^.^...^....
-        ...............
-        ...^.^...^.^...
-        ...............
-        ..^...^.....^..
-        ...............
-        .^.^.^.^.^...^.
-        ...............
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day07::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 21);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day07::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 40);
-    }
+    aoc_runner::example_tests!(Day07);
 }
@@ -1,6 +1,10 @@
 //! # Day 08 Playground
 
-use aoc_runner::Day;
+use std::io::BufRead;
+
+use aoc_runner::{numbered_lines, parse_in_line, Day, Example, ParseError, StreamParser};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 use itertools::Itertools;
 
 /// Coord Element
@@ -15,27 +19,34 @@ type D = I;
 /// The value is choosen experimentally and might be adjusted upwards based on the used input!
 const CUT_OFF: D = 500_000_000;
 
+#[aoc(day = 8)]
 #[derive(Default, Clone)]
 pub struct Day08<const N: usize = 1000> {
     coords: Vec<C>,
     tuples_by_distance: Vec<(D, usize, usize)>,
 }
 
-impl <const N: usize> Day for Day08<N> {
+impl <const N: usize> Day<'_> for Day08<N> {
     type Result1 = usize;
     type Result2 = u64;
 
-    fn parse(&mut self, input: &str) {
-        self.coords = input.lines()
-            .map(|line| {
-                let mut nums = line.split(",").map(|it| it.parse::<I>().unwrap());
-                let x = nums.next().unwrap();
-                let y = nums.next().unwrap();
-                let z = nums.next().unwrap();
-                [x, y, z]
-            })
-            .collect();
+    /// The example's answer of part 1 is only given for connecting the 10 closest pairs
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, if N == 10 { Some("40") } else { None }, Some("25272"))];
+
+    const STREAMING: Option<StreamParser<Self>> = Some(Self::parse_stream);
+
+    const PREPARE: bool = true;
 
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.coords = input.lines().enumerate()
+            .map(|(idx, line)| parse_coord(idx + 1, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
+    }
+
+    /// Sorts the pairs closer than [CUT_OFF] by distance, which both parts connect in this order
+    fn prepare(&mut self) {
         self.tuples_by_distance = {
             let len = self.coords.len();
             let mut distances: Vec<(D, usize, usize)> = Vec::with_capacity(len * len / 2);
@@ -150,45 +161,56 @@ impl <const N: usize> Day for Day08<N> {
     }
 }
 
+impl<const N: usize> Day08<N> {
+    /// Parses the input line by line while it is read, see [Day::STREAMING]
+    fn parse_stream(&mut self, input: &mut dyn BufRead) -> Result<(), ParseError> {
+        self.coords = numbered_lines(input)
+            .map(|line| {
+                let (line_no, line) = line?;
+                parse_coord(line_no, &line)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
+    }
+}
+
+/// Parses the coordinates on the `line_no`th line, e.g. `162,817,812`
+fn parse_coord(line_no: usize, line: &str) -> Result<C, ParseError> {
+    let mut nums = line.split(",");
+    let mut next = || {
+        let num = nums.next().ok_or_else(|| ParseError::in_line(line_no, line, &line[line.len()..], "','"))?;
+        parse_in_line::<I>(line_no, line, num, "a number")
+    };
+    Ok([next()?, next()?, next()?])
+}
+
+const EXAMPLE: &str = indoc! {"
+    162,817,812
+    57,618,57
+    906,360,560
+    592,479,940
+    352,342,300
+    466,668,158
+    542,29,236
+    431,825,988
+    739,650,466
+    52,470,668
+    216,146,977
+    819,987,18
+    117,168,530
+    805,96,715
+    346,949,466
+    970,615,88
+    941,993,340
+    862,61,35
+    984,92,344
+    425,690,689
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        162,817,812
-        57,618,57
-        906,360,560
-        592,479,940
-        352,342,300
-        466,668,158
-        542,29,236
-        431,825,988
-        739,650,466
-        52,470,668
-        216,146,977
-        819,987,18
-        117,168,530
-        805,96,715
-        346,949,466
-        970,615,88
-        941,993,340
-        862,61,35
-        984,92,344
-        425,690,689
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day08::<10>::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 40);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day08::<10>::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 25272);
-    }
+    aoc_runner::example_tests!(Day08<10>);
 }
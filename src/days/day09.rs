@@ -2,34 +2,45 @@
 
 use std::{collections::BTreeMap, iter::once, ops::Range};
 
-use aoc_runner::Day;
+use aoc_runner::{parse_at, Day, Example, ParseError, Tracer};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 use itertools::Itertools;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
 type I = i32;
 type Area = u64;
 
+#[aoc(day = 9)]
 #[derive(Default, Clone)]
 pub struct Day09 {
     polygon: Polygon,
 }
 
-impl Day for Day09 {
+impl Day<'_> for Day09 {
     type Result1 = Area;
     type Result2 = Area;
 
-    fn parse(&mut self, input: &str) {
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("50"), Some("24"))];
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
         let points = input.lines()
             .map(|line| {
-                let (y, x) = line.split_once(",").unwrap();
-                Point {
-                    y: y.parse().unwrap(),
-                    x: x.parse().unwrap(),
-                }
+                let (y, x) = line.split_once(",").ok_or_else(|| ParseError::at(input, line, "a point 'y,x'"))?;
+                Ok(Point {
+                    y: parse_at(input, y, "a number")?,
+                    x: parse_at(input, x, "a number")?,
+                })
             })
-            .collect();
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if points.is_empty() {
+            return Err(ParseError::eof(input, "a point 'y,x'"));
+        }
 
         self.polygon = Polygon::new(points);
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -50,10 +61,13 @@ impl Day for Day09 {
             .rev()
             .collect::<Vec<_>>();
 
+        // counts the checked rectangles, most are usually skipped once the largest valid one is found
+        let counter = Tracer::current().counter(rects_by_area_desc.len());
         rects_by_area_desc
             .into_par_iter()
             .by_exponential_blocks()
             .find_first(|&(_, p1, p2)| {
+                counter.tick();
                 let rect = Points::min_max(p1.rect_points(p2));
 
                 // A line is valid, is not end point of it lies strictly inside the rectangle
@@ -227,35 +241,22 @@ impl Line {
     }
 }
 
+const EXAMPLE: &str = indoc! {"
+    7,1
+    11,1
+    11,7
+    9,7
+    9,5
+    2,5
+    2,3
+    7,3
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        7,1
-        11,1
-        11,7
-        9,7
-        9,5
-        2,5
-        2,3
-        7,3
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day09::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 50);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day09::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 24);
-    }
+    aoc_runner::example_tests!(Day09);
 }
 
 #[allow(unused)]
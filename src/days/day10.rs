@@ -2,20 +2,27 @@
 
 use std::{fmt::Debug,  ops::{Add, Deref, DerefMut, Div, Mul, Sub}, usize};
 
-use aoc_runner::Day;
+use aoc_runner::{parse_at, CancellationToken, Day, Example, ParseError, Tracer};
+use derive_aoc_runner::aoc;
+use indoc::indoc;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
+#[aoc(day = 10)]
 #[derive(Default, Clone)]
 pub struct Day10 {
     machines: Vec<Machine>
 }
 
-impl Day for Day10 {
+impl Day<'_> for Day10 {
     type Result1 = u32;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
-        self.machines = input.lines().map(Machine::from).collect();
+    const EXAMPLES: &'static [Example] = &[(EXAMPLE, Some("7"), Some("33"))];
+
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.machines = input.lines().map(|line| Machine::parse(input, line)).collect::<Result<_, _>>()?;
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -43,15 +50,29 @@ impl Day for Day10 {
     }
 
     fn part2(&mut self) -> Self::Result2 {
+        let token = CancellationToken::current();
+        let counter = Tracer::current().counter(self.machines.len());
         self.machines.par_iter().map(|machine| {
             let mut buttons = MinMaxButton::from_buttons(&machine.joltages, &machine.buttons_index);
-            solve_machine(machine.joltages.clone(), &mut buttons, Limit::max()).unwrap()
+            let Some(presses) = solve_machine(machine.joltages.clone(), &mut buttons, Limit::max(), &token) else {
+                assert!(token.is_cancelled(), "Machine has no solution");
+                return 0;
+            };
+            counter.tick();
+            presses
         })
         .sum()
     }
 }
 
-fn solve_machine(joltages: Joltages, buttons: &mut [MinMaxButton], mut limit: Limit) -> Option<usize> {
+/// Finds the minimal number of button presses to reach `joltages`
+///
+/// Gives up (returning `None`) as soon as `token` is cancelled.
+fn solve_machine(joltages: Joltages, buttons: &mut [MinMaxButton], mut limit: Limit, token: &CancellationToken) -> Option<usize> {
+    if token.is_cancelled() {
+        return None;
+    }
+
     if joltages.is_zero() {
         return Some(0);
     }
@@ -87,7 +108,7 @@ fn solve_machine(joltages: Joltages, buttons: &mut [MinMaxButton], mut limit: Li
 
     (button.min..=button.max).rev().filter_map(|count| {
         let joltages = joltages.subtract_button(&button.button, count)?;
-        let result = solve_machine(joltages, rest, limit - count)?;
+        let result = solve_machine(joltages, rest, limit - count, token)?;
         limit.min_assign(result + count);
         Some(count + result)
     })
@@ -196,50 +217,54 @@ struct Machine {
     joltages: Joltages,
 }
 
-impl <S> From<S> for Machine where S: AsRef<str> {
-    fn from(value: S) -> Self {
+impl Machine {
+    /// Parses a machine from `line`, which must be a line of `input`
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        fn delimited<'a>(input: &str, s: &'a str, open: char, close: char, expected: &str) -> Result<&'a str, ParseError> {
+            s.strip_prefix(open)
+                .and_then(|s| s.strip_suffix(close))
+                .ok_or_else(|| ParseError::at(input, s, expected))
+        }
+
         let mut this = Self::default();
-        let mut parts = value.as_ref().split_whitespace();
+        let mut parts = line.split_whitespace();
         this.pattern = {
-            let s = parts.next().unwrap();
-            s.trim_matches(&['[', ']']).chars().enumerate().fold(0 as Pattern, |acc, (idx, char)| {
-                acc + match char {
+            let s = parts.next().ok_or_else(|| ParseError::at(input, line, "a light pattern '[...]'"))?;
+            let lights = delimited(input, s, '[', ']', "a light pattern '[...]'")?;
+            lights.char_indices().enumerate().try_fold(0 as Pattern, |acc, (idx, (offset, char))| {
+                Ok(acc + match char {
                     '#' => (2 as Pattern).pow(idx as u32),
-                    _ => 0
-                }
-            })
-        };
-        this.buttons_binary = {
-            let btns = parts.clone().take_while(|it| it .starts_with('('));
-            btns.map(|s| {
-                s.trim_matches(&['(', ')'])
-                    .split(',')
-                    .map(|n| n.parse().unwrap())
-                    .fold(0 as Button, |acc, el| {
-                        acc + (2 as Button).pow(el)
-                    })
-            }).collect()
+                    '.' => 0,
+                    _ => return Err(ParseError::at(input, &lights[offset..offset + char.len_utf8()], "'#' or '.'")),
+                })
+            })?
         };
-        this.buttons_index = {
-            let btns = parts.clone().take_while(|it| it .starts_with('('));
-            btns.enumerate().map(|(i, s)| {
-                let v = s.trim_matches(&['(', ')'])
+        let buttons = parts.clone()
+            .take_while(|it| it.starts_with('('))
+            .map(|s| {
+                delimited(input, s, '(', ')', "a button '(...)'")?
                     .split(',')
-                    .map(|n| n.parse().unwrap())
-                    .collect();
-                IndexButton(v, i)
-            }).collect()
-        };
+                    .map(|n| parse_at(input, n, "a number"))
+                    .collect::<Result<Vec<usize>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        this.buttons_binary = buttons.iter().map(|button| {
+            button.iter().fold(0 as Button, |acc, el| {
+                acc + (2 as Button).pow(*el as u32)
+            })
+        }).collect();
+        this.buttons_index = buttons.into_iter().enumerate().map(|(i, v)| IndexButton(v, i)).collect();
         this.joltages = {
-            let v = parts.skip_while(|it| it .starts_with('(')).next().unwrap()
-                .trim_matches(&['{', '}'])
+            let s = parts.find(|it| !it.starts_with('('))
+                .ok_or_else(|| ParseError::at(input, &line[line.len()..], "joltages '{...}'"))?;
+            let v = delimited(input, s, '{', '}', "joltages '{...}'")?
                 .split(',')
-                .map(|it| it.parse().unwrap())
-                .collect();
+                .map(|it| parse_at(input, it, "a number"))
+                .collect::<Result<_, _>>()?;
 
             Joltages(v)
         };
-        this
+        Ok(this)
     }
 }
 
@@ -316,28 +341,15 @@ impl Sub<usize> for Limit {
     }
 }
 
+const EXAMPLE: &str = indoc! {"
+    [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+    [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
+    [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT: &str = indoc!{"
-        [.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
-        [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
-        [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day10::default();
-        day.parse(INPUT);
-        assert_eq!(day.part1(), 7);
-    }
-
-    #[test]
-    fn part_2() {
-        let mut day = Day10::default();
-        day.parse(INPUT);
-        assert_eq!(day.part2(), 33);
-    }
+    aoc_runner::example_tests!(Day10);
 }
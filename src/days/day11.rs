@@ -1,21 +1,31 @@
 //! # Day 11 Reactor
 
-use aoc_runner::Day;
+use aoc_runner::{Day, Example, ParseError};
+use derive_aoc_runner::aoc;
 use fxhash::FxHashMap;
+use indoc::indoc;
 
+#[aoc(day = 11)]
 #[derive(Default, Clone)]
-pub struct Day11 {
-    devices: Vec<Device>,
+pub struct Day11<'input> {
+    devices: Vec<Device<'input>>,
 }
 
-impl Day for Day11 {
+impl<'input> Day<'input> for Day11<'input> {
     type Result1 = usize;
     type Result2 = usize;
 
-    fn parse(&mut self, input: &str) {
+    const EXAMPLES: &'static [Example] = &[
+        (EXAMPLE_1, Some("5"), None),
+        (EXAMPLE_2, None, Some("2")),
+    ];
+
+    fn parse(&mut self, input: &'input str) -> Result<(), ParseError> {
         self.devices = input.lines()
-            .map(Device::from)
-            .collect();
+            .map(|line| Device::parse(input, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -58,66 +68,54 @@ impl Day for Day11 {
 }
 
 #[derive(Default, Clone)]
-struct Device {
-    name: String,
-    out: Vec<String>
+struct Device<'input> {
+    name: &'input str,
+    out: Vec<&'input str>
 }
 
-impl From<&str> for Device {
-    fn from(value: &str) -> Self {
-        let (name, outs) = value.split_once(":").unwrap();
-        Self {
-            name: name.trim().to_string(),
-            out: outs.split_whitespace().map(str::to_string).collect()
-        }
+impl<'input> Device<'input> {
+    /// Parses a device from `line`, which must be a line of `input`
+    fn parse(input: &str, line: &'input str) -> Result<Self, ParseError> {
+        let (name, outs) = line.split_once(":").ok_or_else(|| ParseError::at(input, line, "a device 'name: outputs'"))?;
+        Ok(Self {
+            name: name.trim(),
+            out: outs.split_whitespace().collect()
+        })
     }
 }
 
+const EXAMPLE_1: &str = indoc! {"
+    aaa: you hhh
+    you: bbb ccc
+    bbb: ddd eee
+    ccc: ddd eee fff
+    ddd: ggg
+    eee: out
+    fff: out
+    ggg: out
+    hhh: ccc fff iii
+    iii: out
+"};
+
+const EXAMPLE_2: &str = indoc! {"
+    svr: aaa bbb
+    aaa: fft
+    fft: ccc
+    bbb: tty
+    tty: ccc
+    ccc: ddd eee
+    ddd: hub
+    hub: fff
+    eee: dac
+    dac: fff
+    fff: ggg hhh
+    ggg: out
+    hhh: out
+"};
+
 #[cfg(test)]
 mod test {
-    use indoc::indoc;
     use super::*;
 
-    const INPUT_1: &str = indoc!{"
-        aaa: you hhh
-        you: bbb ccc
-        bbb: ddd eee
-        ccc: ddd eee fff
-        ddd: ggg
-        eee: out
-        fff: out
-        ggg: out
-        hhh: ccc fff iii
-        iii: out
-    "};
-
-    #[test]
-    fn part_1() {
-        let mut day = Day11::default();
-        day.parse(INPUT_1);
-        assert_eq!(day.part1(), 5);
-    }
-
-    const INPUT_2: &str = indoc!{"
-        svr: aaa bbb
-        aaa: fft
-        fft: ccc
-        bbb: tty
-        tty: ccc
-        ccc: ddd eee
-        ddd: hub
-        hub: fff
-        eee: dac
-        dac: fff
-        fff: ggg hhh
-        ggg: out
-        hhh: out
-    "};
-
-    #[test]
-    fn part_2() {
-        let mut day = Day11::default();
-        day.parse(INPUT_2);
-        assert_eq!(day.part2(), 2);
-    }
+    aoc_runner::example_tests!(Day11);
 }
@@ -1,18 +1,26 @@
 //! # Day 12 Christmas Tree Farm
 
-use aoc_runner::Day;
+use aoc_runner::{parse_at, Answer, Day, ParseError};
+use derive_aoc_runner::aoc;
 
+#[aoc(day = 12)]
 #[derive(Default, Clone)]
 pub struct Day12 {
     problems: Vec<Problem>,
 }
 
-impl Day for Day12 {
+impl Day<'_> for Day12 {
     type Result1 = usize;
-    type Result2 = u32;
+    type Result2 = Answer;
 
-    fn parse(&mut self, input: &str) {
-        self.problems = input.split("\n\n").last().unwrap().lines().map(Problem::from).collect();
+    fn parse(&mut self, input: &str) -> Result<(), ParseError> {
+        self.problems = input.split("\n\n").last()
+            .ok_or_else(|| ParseError::eof(input, "a list of regions"))?
+            .lines()
+            .map(|line| Problem::parse(input, line))
+            .collect::<Result<_, _>>()?;
+
+        Ok(())
     }
 
     fn part1(&mut self) -> Self::Result1 {
@@ -24,8 +32,9 @@ impl Day for Day12 {
         }).count()
     }
 
+    /// Day 12 has no second puzzle
     fn part2(&mut self) -> Self::Result2 {
-        0
+        Answer::Unsolved
     }
 }
 
@@ -36,15 +45,16 @@ struct Problem {
     shapes: Vec<usize>
 }
 
-impl From<&str> for Problem {
-    fn from(value: &str) -> Self {
-        let (dimensions, shapes) = value.split_once(":").unwrap();
-        let (width, height) = dimensions.split_once('x').unwrap();
-
-        Self {
-            width: width.parse().unwrap(),
-            height: height.parse().unwrap(),
-            shapes: shapes.split_whitespace().map(|it| it.parse().unwrap()).collect(),
-        }
+impl Problem {
+    /// Parses a problem from `line`, which must be a line of `input`
+    fn parse(input: &str, line: &str) -> Result<Self, ParseError> {
+        let (dimensions, shapes) = line.split_once(":").ok_or_else(|| ParseError::at(input, line, "a region 'WxH: counts'"))?;
+        let (width, height) = dimensions.split_once('x').ok_or_else(|| ParseError::at(input, dimensions, "dimensions 'WxH'"))?;
+
+        Ok(Self {
+            width: parse_at(input, width, "a number")?,
+            height: parse_at(input, height, "a number")?,
+            shapes: shapes.split_whitespace().map(|it| parse_at(input, it, "a number")).collect::<Result<_, _>>()?,
+        })
     }
 }
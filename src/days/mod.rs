@@ -13,21 +13,7 @@ pub mod day10;
 pub mod day11;
 pub mod day12;
 
-use aoc_runner::{Analyzer, Day};
-use derive_aoc_runner::{Analyzer, AoC};
+/// The year of this event
+pub const YEAR: usize = 2025;
 
-#[derive(Analyzer, AoC)]
-pub struct Days(
-    day01::Day01,
-    day02::Day02,
-    day03::Day03,
-    day04::Day04,
-    day05::Day05,
-    day06::Day06,
-    day07::Day07,
-    day08::Day08,
-    day09::Day09,
-    day10::Day10,
-    day11::Day11,
-    day12::Day12,
-);
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
@@ -1,2 +1,39 @@
+use std::ops::{Deref, DerefMut};
+
+use aoc_runner::Calendar;
+use derive_aoc_runner::Analyzer;
+
 pub mod common;
 pub mod days;
+
+/// The days of all years, each annotated with `#[aoc(day = N)]`
+#[derive(Analyzer)]
+pub struct Years(Calendar);
+
+impl Years {
+    pub fn new() -> Self {
+        let mut calendar = Calendar::new();
+        calendar.add(days::YEAR, days::registry());
+        Self(calendar)
+    }
+}
+
+impl Default for Years {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for Years {
+    type Target = Calendar;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Years {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
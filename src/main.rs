@@ -1,54 +1,540 @@
-use std::process::exit;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::exit,
+    time::Duration,
+};
 
-use aoc2025::days::*;
-use itertools::Itertools;
+use aoc2025::Years;
+use aoc_runner::{
+    client::{puzzle_path, Client, Fetched, Submission, DEFAULT_BASE_URL},
+    registry::FIRST_YEAR,
+    scaffold::{puzzle_title, Scaffold},
+    AllocAnalyzer, Analyzer, Answer, AnswerStore, Baseline, CountingAllocator, History, Input, InputDir, InputFile, InputProvider,
+    LogAnalyzer, ParallelAnalyzer, ProgressAnalyzer, Registry, Report, ReportFormat, RunOptions, Stdin, TimeAnalyzer,
+    TraceAnalyzer, Verifier,
+};
+
+/// Counts allocations for the `alloc` analyzer
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator::new();
+
+/// Slowdown in percent beyond which `--compare` flags a phase
+const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// File holding the session cookie of the website, unless it is given in `$AOC_SESSION`
+const SESSION_FILE: &str = "./.session";
+
+/// Directory the puzzle descriptions are downloaded to, see `fetch`
+const PUZZLE_DIR: &str = "./puzzles";
+
+/// Identifies the runner to the website, which asks clients to tell how to reach their users
+const USER_AGENT: &str = "aoc2025 runner by o.herrmann92@gmail.com";
+
+/// Analyzers that can be picked with `--analyzers`
+const ANALYZERS: [&str; 3] = ["time", "alloc", "log"];
+
+/// Command line arguments of the runner
+#[derive(Default)]
+struct Args {
+    year: Option<usize>,
+    day: Option<usize>,
+    part: Option<usize>,
+    input: Option<String>,
+    input_dir: Option<PathBuf>,
+    verify: bool,
+    examples: bool,
+    fetch: bool,
+    submit: bool,
+    new: bool,
+    base_url: Option<String>,
+    check_idempotent: bool,
+    timeout: Option<Duration>,
+    parallel: bool,
+    variant: Option<String>,
+    cross_check: bool,
+    /// Names of the analyzers to run, the ones derived for [Years] if empty
+    analyzers: Vec<String>,
+    report: Option<ReportFormat>,
+    report_file: Option<PathBuf>,
+    /// Run to compare to, `Some(None)` for the most recent runs
+    compare: Option<Option<String>>,
+    /// Save the run times to the history
+    save: bool,
+    threshold: Option<f64>,
+    repeat: usize,
+    warmup: usize,
+    progress: bool,
+    trace: Option<PathBuf>,
+}
 
 fn main() {
-    let args = std::env::args().collect::<Vec<String>>();
-    if args.contains(&"--help".to_owned()) || args.contains(&"-h".to_owned()) {
-        usage();
-        exit(0);
-    }
-
-    let mut days = Days::new();
-    let (day, part) = get_args();
-    if let Some(day) = day {
-        let args = std::env::args().collect::<Vec<String>>();
-        let input = if let Some(input_file_path) = args
-            .iter()
-            .find_position(|it| it == &"-i" || it == &"--input")
-            .map(|(idx, _)| idx + 1)
-            .and_then(|idx| args.get(idx))
-        {
-            std::fs::read_to_string(input_file_path)
-                .unwrap_or_else(|_| panic!("File not found: {}", input_file_path))
-        } else {
-            std::io::stdin()
-                .lines()
-                .map(|line| line.unwrap())
-                .collect::<Vec<String>>()
-                .join("\n")
-        };
-
-        days.run_part(day, part, &input, &mut days.get_analyzer());
+    let args = get_args();
+
+    let years = Years::new();
+    let year = args.year.or(years.latest()).expect("No days registered");
+    let Some(days) = years.get(year) else {
+        let available = years.years().map(|year| year.to_string()).collect::<Vec<_>>();
+        eprintln!("Year {} is not implemented. Available years are: {}", year, available.join(", "));
+        exit(1);
+    };
+
+    let succeeded = if args.examples {
+        days.run_examples(args.day)
+    } else if args.check_idempotent {
+        check_idempotent(days, year, &args)
+    } else if args.fetch {
+        fetch(days, year, &args)
+    } else if args.submit {
+        submit(days, year, &args)
+    } else if args.new {
+        new_day(year, &args)
+    } else if args.analyzers.is_empty() {
+        // the analyzers derived for the puzzles, unless --analyzers picks others
+        let mut analyzer = measure(year, &args, years.get_analyzer());
+        match args.parallel {
+            true => run_parallel(days, year, &args, &mut analyzer),
+            false => run(days, year, &args, &mut analyzer),
+        }
     } else {
-        let inputs = (1..=days.len())
-            .map(|idx| {
-                let input_file_path = format!("./input/{:0>2}.txt", idx);
-                std::fs::read_to_string(input_file_path).ok()
-            })
-            .collect::<Vec<Option<String>>>();
-        days.run_some(&inputs[..]);
+        // the time analyzer comes last, so that the other analyzers' hooks are not part of its times
+        let log = args.analyzes("log").then(LogAnalyzer::new);
+        let time = args.analyzes("time").then(TimeAnalyzer::new);
+        if args.parallel {
+            // the allocations of days running at the same time can't be told apart
+            if args.analyzes("alloc") {
+                eprintln!("The alloc analyzer can't tell the allocations of days running in parallel apart. Leave out --parallel");
+                exit(1);
+            }
+            run_parallel(days, year, &args, &mut measure(year, &args, (log, time)))
+        } else {
+            let alloc = args.analyzes("alloc").then(AllocAnalyzer::new);
+            run(days, year, &args, &mut measure(year, &args, (log, alloc, time)))
+        }
+    };
+    if !succeeded {
+        exit(1);
+    }
+}
+
+impl Args {
+    /// Returns true if the analyzer with the given name was picked
+    fn analyzes(&self, name: &str) -> bool {
+        if self.analyzers.is_empty() {
+            name == "time"
+        } else {
+            self.analyzers.iter().any(|analyzer| analyzer == name)
+        }
+    }
+
+    /// Returns true if a machine-readable report is written to stdout, which then holds nothing else
+    fn reports_to_stdout(&self) -> bool {
+        self.report_file.is_none() && self.report.is_some_and(|format| format != ReportFormat::Table)
+    }
+
+    /// A client of the website, logged in with the session from `$AOC_SESSION` or [SESSION_FILE]
+    fn client(&self) -> Client {
+        let session = std::env::var("AOC_SESSION").or_else(|_| fs::read_to_string(SESSION_FILE)).unwrap_or_else(|_| {
+            eprintln!("No session found. Store the session cookie of the website in {} or $AOC_SESSION", SESSION_FILE);
+            exit(1);
+        });
+        let base_url = self.base_url.clone().or_else(|| std::env::var("AOC_BASE_URL").ok());
+        Client::new(session)
+            .with_base_url(base_url.unwrap_or_else(|| DEFAULT_BASE_URL.to_string()))
+            .with_user_agent(USER_AGENT)
+    }
+
+    /// Where the inputs are read from: the file given with `--input` for a single day, else the
+    /// directory given with `--input-dir`, else stdin for a single day and `./input` for all days
+    fn input_provider(&self) -> Box<dyn InputProvider> {
+        match (&self.input, &self.input_dir, self.day) {
+            (Some(path), _, Some(_)) => Box::new(InputFile::new(path)),
+            (_, Some(dir), _) => Box::new(InputDir::new(dir)),
+            (_, None, Some(_)) => Box::new(Stdin),
+            (_, None, None) => Box::new(InputDir::default()),
+        }
     }
 }
 
-fn get_args() -> (Option<usize>, Option<usize>) {
-    let args = std::env::args().collect::<Vec<String>>();
-    let day = args
-        .get(1)
-        .map(|arg| arg.parse().expect("'day' must be a number"));
-    let part = args.get(2).and_then(|arg| arg.parse().ok());
-    (day, part)
+/// Loads the run times of `run` from `history`, or the most recent ones if no run is given
+fn load_baseline(history: &History, run: Option<&str>) -> Baseline {
+    let runs = history.load().unwrap_or_else(|err| panic!("Could not read history: {}", err));
+    match run {
+        Some(run) => Baseline::of(&runs, run).unwrap_or_else(|| {
+            let available = runs.iter().map(|run| run.id.as_str()).collect::<Vec<_>>();
+            eprintln!("Run {} is not in the history. Available runs are: {}", run, available.join(", "));
+            exit(1);
+        }),
+        None => Baseline::latest(&runs),
+    }
+}
+
+/// `analyzers` along with the verifier, progress and trace analyzers if requested
+type Measured<A> = (Option<Verifier<()>>, Option<ProgressAnalyzer>, Option<TraceAnalyzer>, A);
+
+/// Adds the verifier, progress and trace analyzers to `analyzers` if requested
+///
+/// Applies the options of the time analyzer to the one among `analyzers`. Exits if they are given
+/// without one. The verifier comes first, so that its table follows the reports of the others.
+fn measure<A: Analyzer>(year: usize, args: &Args, mut analyzers: A) -> Measured<A> {
+    let configured = args.report.is_some() || args.report_file.is_some() || args.compare.is_some() || args.save;
+    match analyzers.time_analyzer() {
+        Some(time) => *time = configure_time(std::mem::take(time), year, args),
+        None if configured => {
+            eprintln!("--report, --report-file, --compare and --save only apply to run times. Add time to the analyzers, e.g. --analyzers time,alloc");
+            exit(1);
+        }
+        None => {}
+    }
+
+    let verifier = args.verify.then(|| Verifier::new((), AnswerStore::new(format!("./answers/{}", year))));
+    let progress = args.progress.then(ProgressAnalyzer::new);
+    let trace = args.trace.as_ref().map(|path| {
+        TraceAnalyzer::create(path).unwrap_or_else(|err| panic!("Could not create {}: {}", path.display(), err))
+    });
+    (verifier, progress, trace, analyzers)
+}
+
+/// Applies the report, comparison and history options to `analyzer`
+fn configure_time(analyzer: TimeAnalyzer, year: usize, args: &Args) -> TimeAnalyzer {
+    let history = History::new(format!("./history/{}.csv", year));
+    let mut analyzer = analyzer.with_report(Report {
+        format: args.report.or_else(|| args.report_file.as_deref().map(format_of)).unwrap_or_default(),
+        path: args.report_file.clone(),
+    });
+    if let Some(run) = &args.compare {
+        let baseline = load_baseline(&history, run.as_deref());
+        analyzer = analyzer.compare_to(baseline, args.threshold.unwrap_or(DEFAULT_THRESHOLD));
+    }
+    if args.save {
+        analyzer = analyzer.with_history(history);
+    }
+    analyzer
+}
+
+/// Runs the requested day, or all days of `year` one after another
+///
+/// Returns true if all of them finished successfully and no answer was wrong.
+fn run(days: &Registry, year: usize, args: &Args, analyzer: &mut Measured<impl Analyzer>) -> bool {
+    let options = run_options(args);
+    let provider = args.input_provider();
+    let succeeded = match args.day {
+        Some(day) => days.run_part(day, args.part, day_input(&provider, year, day), &options, analyzer),
+        None => days.run_some(all_inputs(&provider, year), &options, analyzer),
+    };
+    succeeded && verified(analyzer)
+}
+
+/// Like [run], but runs all days of `year` at the same time
+fn run_parallel(days: &Registry, year: usize, args: &Args, analyzer: &mut Measured<impl ParallelAnalyzer>) -> bool {
+    if args.day.is_some() {
+        return run(days, year, args, analyzer);
+    }
+    let options = run_options(args);
+    let succeeded = days.run_some_parallel(all_inputs(&args.input_provider(), year), &options, analyzer);
+    succeeded && verified(analyzer)
+}
+
+/// Returns false if the verifier, if any, found a wrong answer
+fn verified(analyzer: &Measured<impl Analyzer>) -> bool {
+    !analyzer.0.as_ref().is_some_and(|verifier| verifier.failed())
+}
+
+fn run_options(args: &Args) -> RunOptions {
+    RunOptions {
+        timeout: args.timeout,
+        variant: args.variant.clone(),
+        cross_check: args.cross_check,
+        repeat: args.repeat,
+        warmup: args.warmup,
+        trace: args.progress || args.trace.is_some(),
+        quiet: args.reports_to_stdout(),
+    }
+}
+
+/// Checks that the requested days of `year` give the same answers however their phases run
+fn check_idempotent(days: &Registry, year: usize, args: &Args) -> bool {
+    let provider = args.input_provider();
+    let inputs = match args.day {
+        Some(day) => {
+            let mut inputs = (1..day).map(|_| None).collect::<Vec<_>>();
+            inputs.push(Some(day_input(&provider, year, day)));
+            inputs
+        }
+        None => all_inputs(&provider, year),
+    };
+    days.check_idempotent(inputs)
+}
+
+/// Downloads the missing inputs and descriptions of the requested days of `year`, all implemented
+/// days by default
+///
+/// Returns true if all of them are in the input directory afterwards.
+fn fetch(days: &Registry, year: usize, args: &Args) -> bool {
+    let dir = args.input_dir.as_ref().map(InputDir::new).unwrap_or_default();
+    let mut client = args.client();
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => days.days().collect(),
+    };
+
+    let mut succeeded = true;
+    for day in days {
+        let path = dir.path(year, day);
+        match client.fetch_input(year, day, &dir) {
+            Ok(Fetched::Cached) => println!("Day {}: cached in {}", day, path.display()),
+            Ok(Fetched::Downloaded) => println!("Day {}: downloaded to {}", day, path.display()),
+            Err(err) => {
+                eprintln!("Day {}: could not download input, {}", day, err);
+                succeeded = false;
+            }
+        }
+        // descriptions are only needed for the titles of new days
+        if let Err(err) = client.fetch_puzzle(year, day, Path::new(PUZZLE_DIR)) {
+            eprintln!("Day {}: could not download description, {}", day, err);
+        }
+    }
+    succeeded
+}
+
+/// Creates the module of a new day of `year`, titled like its description if it was fetched
+fn new_day(year: usize, args: &Args) -> bool {
+    let Some(day) = args.day else {
+        eprintln!("Creating a day needs its number, e.g. new 7");
+        exit(1);
+    };
+    // the other years' days live in directories of their own, which are not scaffolded
+    if year != aoc2025::days::YEAR {
+        eprintln!("Only days of {} can be created, their modules are in ./src/days", aoc2025::days::YEAR);
+        exit(1);
+    }
+
+    let title = fs::read_to_string(puzzle_path(Path::new(PUZZLE_DIR), year, day))
+        .ok()
+        .and_then(|page| puzzle_title(&page));
+    let scaffold = Scaffold {
+        krate: env!("CARGO_CRATE_NAME").to_string(),
+        days_dir: PathBuf::from("./src/days"),
+        bench: Some(PathBuf::from("./benches/aoc_2025_bench.rs")),
+    };
+    match scaffold.create_day(day, title.as_deref()) {
+        Ok(changed) => {
+            for path in changed {
+                println!("Changed {}", path.display());
+            }
+            if title.is_none() {
+                println!("The description of day {} was not fetched, so the day has no title", day);
+            }
+            true
+        }
+        Err(err) => {
+            eprintln!("Could not create day {}: {}", day, err);
+            false
+        }
+    }
+}
+
+/// Remembers the last answer given
+#[derive(Default)]
+struct LastAnswer(Option<Answer>);
+
+impl Analyzer for LastAnswer {
+    fn on_answer(&mut self, _day: usize, _part: usize, answer: &Answer) {
+        self.0 = Some(answer.clone());
+    }
+}
+
+/// Runs the requested part of `year` and submits its answer, unless it is known to be wrong
+///
+/// Records the website's verdict in `./answers/<year>` and returns true if the answer is correct.
+fn submit(days: &Registry, year: usize, args: &Args) -> bool {
+    let (Some(day), Some(part @ (1 | 2))) = (args.day, args.part) else {
+        eprintln!("Submitting needs a day and a part, e.g. submit 7 2");
+        exit(1);
+    };
+
+    let options = RunOptions {
+        timeout: args.timeout,
+        variant: args.variant.clone(),
+        ..Default::default()
+    };
+    // unlike running a single day, submitting reads the input directory rather than stdin
+    let provider: Box<dyn InputProvider> = match &args.input {
+        Some(path) => Box::new(InputFile::new(path)),
+        None => Box::new(args.input_dir.as_ref().map(InputDir::new).unwrap_or_default()),
+    };
+    let mut answer = LastAnswer::default();
+    let input = day_input(&provider, year, day);
+    if !days.run_part(day, Some(part), input, &options, &mut answer) {
+        return false;
+    }
+    let answer = match answer.0 {
+        Some(Answer::Unsolved) | None => {
+            eprintln!(" - Part {} is not solved yet, so there is nothing to submit", part);
+            return false;
+        }
+        Some(Answer::Grid(_)) => {
+            eprintln!(" - Grids cannot be submitted, read the answer off the grid and submit it on the website");
+            return false;
+        }
+        Some(answer) => answer,
+    };
+
+    let store = AnswerStore::new(format!("./answers/{}", year));
+    let mut answers = store.load(day).unwrap_or_else(|err| {
+        eprintln!("Could not load answers: {}", err);
+        exit(1);
+    });
+    if let Some(known) = answers.get(part) {
+        if answer.matches(known) {
+            println!(" - {} is the known answer, so it is not submitted again", answer);
+            return true;
+        }
+        eprintln!(" - Not submitting {}, the known answer is {}", answer, known);
+        return false;
+    }
+    if let Some(reason) = answers.rejected(part).reason(&answer) {
+        eprintln!(" - Not submitting {}, it is known to be wrong: {}", answer, reason);
+        return false;
+    }
+
+    let answer = answer.to_string();
+    let submission = args.client().submit(year, day, part, &answer).unwrap_or_else(|err| {
+        eprintln!(" - Could not submit {}: {}", answer, err);
+        exit(1);
+    });
+    println!(" - Submitted {}: {}", answer, submission);
+    match submission {
+        Submission::Correct => answers.set(part, &answer),
+        Submission::Rejected(rejection) => answers.rejected_mut(part).add(&answer, rejection),
+        Submission::Wait(_) | Submission::WrongLevel => return false,
+    }
+    if let Err(err) = store.save(day, &answers) {
+        eprintln!("Could not record the answer: {}", err);
+    }
+
+    submission == Submission::Correct
+}
+
+/// The input of a single day, exiting if there is none
+fn day_input(provider: &impl InputProvider, year: usize, day: usize) -> Input<'static> {
+    match provider.input(year, day) {
+        Ok(Some(input)) => input,
+        Ok(None) => {
+            eprintln!("No input for day {} of {}", day, year);
+            exit(1);
+        }
+        Err(err) => {
+            eprintln!("Could not open input of day {} of {}: {}", day, year, err);
+            exit(1);
+        }
+    }
+}
+
+/// The inputs of all days of `year`, exiting if one cannot be opened
+fn all_inputs(provider: &impl InputProvider, year: usize) -> Vec<Option<Input<'static>>> {
+    provider.inputs(year).unwrap_or_else(|err| {
+        eprintln!("Could not open inputs of {}: {}", year, err);
+        exit(1);
+    })
+}
+
+fn get_args() -> Args {
+    let mut args = Args::default();
+    let mut positional = vec![];
+    let mut iter = std::env::args().skip(1);
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => {
+                usage();
+                exit(0);
+            }
+            "-i" | "--input" => args.input = iter.next(),
+            "--input-dir" => args.input_dir = iter.next().map(PathBuf::from),
+            "--verify" => args.verify = true,
+            "--examples" => args.examples = true,
+            "fetch" if positional.is_empty() => args.fetch = true,
+            "submit" if positional.is_empty() => args.submit = true,
+            "new" if positional.is_empty() => args.new = true,
+            "--base-url" => args.base_url = iter.next(),
+            "--check-idempotent" => args.check_idempotent = true,
+            "--parallel" => args.parallel = true,
+            "--variant" => args.variant = iter.next(),
+            "--cross-check" => args.cross_check = true,
+            "--alloc" => args.analyzers = vec!["alloc".to_string()],
+            "--analyzers" => {
+                let value = iter.next().unwrap_or_default();
+                args.analyzers = value.split(',').map(|name| name.trim().to_string()).collect();
+                if let Some(name) = args.analyzers.iter().find(|name| !ANALYZERS.contains(&name.as_str())) {
+                    panic!("Invalid analyzer: '{}'. Valid analyzers are: {}", name, ANALYZERS.join(", "));
+                }
+            }
+            "--report" => {
+                let value = iter.next().unwrap_or_default();
+                args.report = Some(value.parse().unwrap_or_else(|err| panic!("{}", err)));
+            }
+            "--report-file" => args.report_file = iter.next().map(PathBuf::from),
+            "--compare" => args.compare = Some(None),
+            "--save" => args.save = true,
+            "--repeat" => args.repeat = parse_count("--repeat", iter.next()),
+            "--warmup" => args.warmup = parse_count("--warmup", iter.next()),
+            "--progress" => args.progress = true,
+            "--trace" => args.trace = iter.next().map(PathBuf::from),
+            "--threshold" => {
+                let value = iter.next().unwrap_or_default();
+                let threshold = value.trim_end_matches('%').parse()
+                    .unwrap_or_else(|_| panic!("Invalid threshold: '{}'. Expected a percentage, e.g. 10", value));
+                args.threshold = Some(threshold);
+            }
+            arg if arg.starts_with("--compare=") => args.compare = Some(Some(arg["--compare=".len()..].to_string())),
+            "--timeout" => {
+                let value = iter.next().unwrap_or_default();
+                let timeout = parse_duration(&value)
+                    .unwrap_or_else(|| panic!("Invalid timeout: '{}'. Expected e.g. 500ms, 10s or 2m", value));
+                args.timeout = Some(timeout);
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional
+        .iter()
+        .map(|arg| arg.parse::<usize>().unwrap_or_else(|_| panic!("'{}' must be a number", arg)))
+        .peekable();
+    args.year = positional.next_if(|year| *year >= FIRST_YEAR);
+    args.day = positional.next();
+    args.part = positional.next();
+    if args.reports_to_stdout() && (args.verify || args.analyzes("alloc")) {
+        panic!("--verify and the alloc analyzer print tables, which would be mixed into the report. Write it to a file with --report-file");
+    }
+    args
+}
+
+/// Parses the number of runs given for `flag`
+fn parse_count(flag: &str, value: Option<String>) -> usize {
+    let value = value.unwrap_or_default();
+    value.parse().unwrap_or_else(|_| panic!("Invalid value of {}: '{}'. Expected a number of runs", flag, value))
+}
+
+/// The report format matching the extension of `path`
+fn format_of(path: &Path) -> ReportFormat {
+    let extension = path.extension().and_then(|extension| extension.to_str()).unwrap_or_default();
+    extension.parse().unwrap_or_else(|_| panic!("Unknown report format of '{}'. Use --report <format>", path.display()))
+}
+
+/// Parses a duration like `250us`, `500ms`, `1.5s` or `2m`
+fn parse_duration(s: &str) -> Option<Duration> {
+    let idx = s.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (value, unit) = s.split_at(idx);
+    let value: f64 = value.parse().ok()?;
+    let secs = match unit {
+        "us" | "μs" => value / 1_000_000.0,
+        "ms" => value / 1_000.0,
+        "s" => value,
+        "m" => value * 60.0,
+        _ => return None,
+    };
+    Duration::try_from_secs_f64(secs).ok()
 }
 
 fn usage() {
@@ -60,5 +546,44 @@ fn usage() {
             Some(name_str.to_string())
         })
         .unwrap_or_else(|| String::from("<binary>"));
-    println!("USAGE: {} [day] [part]", binary_name);
+    println!("USAGE: {} [year] [day] [part] [-i|--input <file>] [--input-dir <dir>] [--verify] [--timeout <duration>] [--variant <name>] [--cross-check] [--analyzers <names>] [--report <format>] [--report-file <file>]", binary_name);
+    println!("       {} [year] [day] [part] [--save] [--compare[=<run>]] [--threshold <percent>]", binary_name);
+    println!("       {} [year] [day] [part] --repeat <runs> [--warmup <runs>]", binary_name);
+    println!("       {} [year] [--input-dir <dir>] [--verify] [--timeout <duration>] [--parallel] [--variant <name>] [--cross-check] [--analyzers <names>] [--report <format>] [--report-file <file>]", binary_name);
+    println!("       {} [year] [day] [part] [--progress] [--trace <file>]", binary_name);
+    println!("       {} --examples [year] [day]", binary_name);
+    println!("       {} fetch [year] [day] [--input-dir <dir>] [--base-url <url>]", binary_name);
+    println!("       {} submit [year] <day> <part> [-i|--input <file>] [--input-dir <dir>] [--base-url <url>]", binary_name);
+    println!("       {} new [year] <day>", binary_name);
+    println!("       {} --check-idempotent [year] [day] [-i|--input <file>] [--input-dir <dir>]", binary_name);
+    println!();
+    println!("  year           Defaults to the most recent year. Inputs are read from ./input/<year>/NN.txt");
+    println!("  day            A single day reads its input from --input, --input-dir or stdin, in this order");
+    println!("  --input-dir    Read inputs from <dir>/<year>/NN.txt instead of ./input/<year>/NN.txt");
+    println!("  --verify       Compare answers to the ones recorded in ./answers/<year>/NN.toml");
+    println!("  --examples     Run the examples from the puzzle descriptions");
+    println!("  fetch          Download the inputs and descriptions of all implemented days, or of the given day, that were");
+    println!("                 not downloaded yet. Descriptions are kept in ./puzzles/<year>/NN.html");
+    println!("                 Logs in with the session cookie from ./.session or $AOC_SESSION");
+    println!("  submit         Run the part and submit its answer, unless it is known to be wrong. Records the verdict in");
+    println!("                 ./answers/<year>/NN.toml");
+    println!("  new            Create src/days/dayNN.rs from a template and add it to src/days/mod.rs and the benchmarks");
+    println!("  --base-url     Download from and submit to this URL instead of $AOC_BASE_URL or https://adventofcode.com");
+    println!("  --check-idempotent");
+    println!("                 Parse each input twice and run each part twice in both orders and list days giving other answers");
+    println!("  --timeout      Cancel each part after the given duration, e.g. 500ms, 10s or 2m");
+    println!("  --parallel     Run all days at the same time");
+    println!("  --variant      Run the named variant of each part instead of its default implementation, if it has one");
+    println!("  --cross-check  Run all variants of each part and fail if their answers differ");
+    println!("  --analyzers    Comma-separated analyzers to run at once: time, alloc and log, e.g. time,alloc. Defaults to the ones derived for Years");
+    println!("  --alloc        Report the allocations of each phase instead of its run time, same as --analyzers alloc. Not with --parallel");
+    println!("  --report       Report run times as table (default), json, csv or markdown, with times in nanoseconds");
+    println!("  --report-file  Write the report to a file instead of stdout. Its extension names the format if --report is missing");
+    println!("  --save         Append the run times and answers to ./history/<year>.csv");
+    println!("  --compare      Compare run times to the given run from ./history/<year>.csv, or to the most recent runs of the same build profile");
+    println!("  --threshold    Flag phases that slowed down by more than this percentage when comparing, defaults to 10");
+    println!("  --repeat       Measure each day this many times on fresh instances and report the statistics of its phases");
+    println!("  --warmup       Run each day this many times without measuring before the measured runs");
+    println!("  --progress     Show a progress bar for parts reporting their progress. Parts then run on a worker thread");
+    println!("  --trace        Write the progress and messages parts send while they run to a file, one JSON object per line");
 }